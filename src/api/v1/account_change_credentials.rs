@@ -35,7 +35,7 @@ pub async fn api_account_change_credentials(
         // verify message
         let public_key = msg.public_key()?.to_owned();
         let msg = msg.verify()?;
-        let msg = serde_json::from_slice::<MsgAccountChangeCredentials>(&msg)?;
+        let msg = serde_json::from_slice::<MsgAccountChangeCredentials>(msg)?;
 
         if &msg.new_algo != "ed25519" {
             return Err(anyhow::anyhow!("Unsupported algorithm: {}", &msg.new_algo));
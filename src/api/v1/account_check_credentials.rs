@@ -28,7 +28,7 @@ pub async fn api_account_check_credentials(
         // verify message
         let public_key = msg.public_key()?.to_owned();
         let msg = msg.verify()?;
-        serde_json::from_slice::<MsgAccountCheckCredentials>(&msg)?;
+        serde_json::from_slice::<MsgAccountCheckCredentials>(msg)?;
 
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
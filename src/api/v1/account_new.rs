@@ -33,14 +33,14 @@ pub async fn api_account_new(
     result_into_response(async move {
         let public_key = msg.public_key()?.to_owned();
         let msg = msg.verify()?;
-        let msg = serde_json::from_slice::<MsgAccountNew>(&msg)?;
+        let msg = serde_json::from_slice::<MsgAccountNew>(msg)?;
         let invite = base64::decode(&msg.invite)?;
         let signing_key = state.primary_secret.derive_secret("signing_key");
         let invite: SignedMessage = serde_json::from_slice(&invite)?;
 
         let key = PrivateKey::from_bytes("hmac-sha256", &signing_key)?;
         let invite_msg = invite.verify_with_secret(key)?;
-        let invite_msg = serde_json::from_slice::<Invite>(&invite_msg)?;
+        let invite_msg = serde_json::from_slice::<Invite>(invite_msg)?;
 
         if msg.name.len() > limits::MAX_ITEM_NAME_SIZE {
             return Err(anyhow::anyhow!("Name is too long"));
@@ -59,7 +59,7 @@ pub async fn api_account_new(
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
-        if let Ok(_) = trx.query_row("SELECT id FROM author WHERE uuid = ?", [uuid], |row| row.get::<_, u32>(0)) {
+        if trx.query_row("SELECT id FROM author WHERE uuid = ?", [uuid], |row| row.get::<_, u32>(0)).is_ok() {
             return Err(anyhow::anyhow!("Author already exists"));
         }
 
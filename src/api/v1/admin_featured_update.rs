@@ -0,0 +1,48 @@
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{State, Query},
+    response::IntoResponse,
+    Json,
+};
+
+use serde::{Serialize, Deserialize};
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::crypto;
+use crate::pins;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MsgFeaturedUpdate {
+    /// The complete list of featured posts, shown first to last.
+    post_uuids: Vec<String>,
+}
+
+pub async fn api_admin_featured_update(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+    Json(msg): Json<MsgFeaturedUpdate>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        let admin_token = state.primary_secret.derive_secret("admin_token");
+        let token = hex::decode(params.get("token").ok_or_else(|| anyhow::anyhow!("Missing token parameter"))?)?;
+
+        if !crypto::constant_time_eq(&admin_token, &token) {
+            return Err(anyhow::anyhow!("Invalid token"));
+        }
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        pins::set_featured_posts(&trx, &msg.post_uuids)?;
+        trx.commit()?;
+
+        Ok(Json(serde_json::json!({
+            "status": "ok",
+        })))
+    }, ErrorReporting::Json).await
+}
@@ -10,6 +10,7 @@ use axum::{
 
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::api::v1::types::normalize_language_code;
 
 
 pub async fn api_admin_meta_delete(
@@ -27,10 +28,18 @@ pub async fn api_admin_meta_delete(
 
         let page_name = params.get("page_name").ok_or_else(|| anyhow::anyhow!("Missing page_name parameter"))?;
 
+        // without lang, all language variants are deleted
+        // the revisions are kept, so that rolling back to one of them restores the variant
+        let lang = params.get("lang").map(|lang| normalize_language_code(lang)).transpose()?;
+
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
-        trx.execute("DELETE FROM meta_page WHERE page_name = ?", [page_name])?;
+        if let Some(lang) = lang {
+            trx.execute("UPDATE meta_page SET is_deleted = 1 WHERE page_name = ? AND language_code = ?", (page_name, lang))?;
+        } else {
+            trx.execute("UPDATE meta_page SET is_deleted = 1 WHERE page_name = ?", [page_name])?;
+        }
         trx.commit()?;
 
         Ok(Json(serde_json::json!({
@@ -0,0 +1,65 @@
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{State, Query},
+    response::IntoResponse,
+    Json,
+};
+use hyper::StatusCode;
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::crypto;
+
+
+pub async fn api_admin_meta_revision_info(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        let admin_token = state.primary_secret.derive_secret("admin_token");
+        let token = hex::decode(params.get("token").ok_or_else(|| anyhow::anyhow!("Missing token parameter"))?)?;
+
+        if !crypto::constant_time_eq(&admin_token, &token) {
+            return Err(anyhow::anyhow!("Invalid token"));
+        }
+
+        let revision_uuid = params.get("uuid").ok_or_else(|| anyhow::anyhow!("Missing uuid parameter"))?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let (page_name, lang, created_date, title, page_text) = if let Ok(values) = trx.query_row(
+            "SELECT meta_page.page_name, meta_page.language_code, meta_page_revision.created_date, meta_page_revision.title, meta_page_revision.page_text FROM meta_page, meta_page_revision WHERE meta_page_revision.uuid = ? AND meta_page_revision.meta_page_id = meta_page.id",
+            [revision_uuid],
+            |row| {
+                let page_name: String = row.get(0)?;
+                let lang: String = row.get(1)?;
+                let created_date: u64 = row.get(2)?;
+                let title: String = row.get(3)?;
+                let page_text: String = row.get(4)?;
+                Ok((page_name, lang, created_date, title, page_text))
+            },
+        ) {
+            values
+        } else {
+            return Ok((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({
+                    "status": "not found",
+                })),
+            ).into_response());
+        };
+
+        Ok(Json(serde_json::json!({
+            "revision_uuid": revision_uuid,
+            "page_name": page_name,
+            "lang": if lang.is_empty() { None } else { Some(lang) },
+            "created_date": created_date,
+            "title": title,
+            "text": page_text,
+        })).into_response())
+    }, ErrorReporting::Json).await
+}
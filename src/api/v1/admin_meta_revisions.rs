@@ -0,0 +1,57 @@
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{State, Query},
+    response::IntoResponse,
+    Json,
+};
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::crypto;
+use crate::api::v1::types::normalize_language_code;
+
+
+pub async fn api_admin_meta_revisions(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        let admin_token = state.primary_secret.derive_secret("admin_token");
+        let token = hex::decode(params.get("token").ok_or_else(|| anyhow::anyhow!("Missing token parameter"))?)?;
+
+        if !crypto::constant_time_eq(&admin_token, &token) {
+            return Err(anyhow::anyhow!("Invalid token"));
+        }
+
+        let page_name = params.get("page_name").ok_or_else(|| anyhow::anyhow!("Missing page_name parameter"))?;
+
+        // the exact variant, without fallback
+        let lang = params.get("lang").map(|lang| normalize_language_code(lang)).transpose()?.unwrap_or_default();
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let mut stmt = trx.prepare(
+            "SELECT meta_page_revision.uuid, meta_page_revision.created_date, meta_page_revision.title FROM meta_page, meta_page_revision WHERE meta_page.page_name = ? AND meta_page.language_code = ? AND meta_page_revision.meta_page_id = meta_page.id ORDER BY meta_page_revision.created_date DESC, meta_page_revision.id DESC LIMIT 1000"
+        )?;
+        let mut rows = stmt.query((page_name, &lang))?;
+
+        let mut revisions = Vec::<serde_json::Value>::new();
+        while let Some(row) = rows.next()? {
+            let revision_uuid: String = row.get(0)?;
+            let created_date: u64 = row.get(1)?;
+            let title: String = row.get(2)?;
+
+            revisions.push(serde_json::json!({
+                "revision_uuid": revision_uuid,
+                "created_date": created_date,
+                "title": title,
+            }));
+        }
+
+        Ok(Json(serde_json::json!(revisions)))
+    }, ErrorReporting::Json).await
+}
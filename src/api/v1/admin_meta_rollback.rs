@@ -0,0 +1,57 @@
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{State, Query},
+    response::IntoResponse,
+    Json,
+};
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::crypto;
+use crate::sys_time;
+use crate::meta_pages;
+
+
+pub async fn api_admin_meta_rollback(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        let admin_token = state.primary_secret.derive_secret("admin_token");
+        let token = hex::decode(params.get("token").ok_or_else(|| anyhow::anyhow!("Missing token parameter"))?)?;
+
+        if !crypto::constant_time_eq(&admin_token, &token) {
+            return Err(anyhow::anyhow!("Invalid token"));
+        }
+
+        let revision_uuid = params.get("uuid").ok_or_else(|| anyhow::anyhow!("Missing uuid parameter"))?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let (page_name, lang, title, page_text) = trx.query_row(
+            "SELECT meta_page.page_name, meta_page.language_code, meta_page_revision.title, meta_page_revision.page_text FROM meta_page, meta_page_revision WHERE meta_page_revision.uuid = ? AND meta_page_revision.meta_page_id = meta_page.id",
+            [revision_uuid],
+            |row| {
+                let page_name: String = row.get(0)?;
+                let lang: String = row.get(1)?;
+                let title: String = row.get(2)?;
+                let page_text: String = row.get(3)?;
+                Ok((page_name, lang, title, page_text))
+            },
+        ).map_err(|_| anyhow::anyhow!("Revision not found"))?;
+
+        // the old content becomes a new revision, so the history is kept intact
+        let time = sys_time::get_sys_time_in_secs();
+        let new_revision_uuid = meta_pages::save_meta_page(&trx, &page_name, &lang, &title, &page_text, time)?;
+        trx.commit()?;
+
+        Ok(Json(serde_json::json!({
+            "status": "ok",
+            "revision_uuid": new_revision_uuid,
+        })))
+    }, ErrorReporting::Json).await
+}
@@ -14,7 +14,8 @@ use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
 use crate::sys_time;
 use crate::limits;
-use crate::api::v1::types::is_valid_dns_token;
+use crate::meta_pages;
+use crate::api::v1::types::{is_valid_dns_token, normalize_language_code};
 
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +23,10 @@ pub struct MsgMetaUpdate {
     page_name: String,
     title: String,
     text: String,
+
+    /// The default variant is updated if omitted.
+    #[serde(default)]
+    lang: Option<String>,
 }
 
 pub async fn api_admin_meta_update(
@@ -51,6 +56,8 @@ pub async fn api_admin_meta_update(
             return Err(anyhow::anyhow!("Text is too long"));
         }
 
+        let lang = msg.lang.as_deref().map(normalize_language_code).transpose()?.unwrap_or_default();
+
         let title = &msg.title;
         let text = &msg.text;
         let time = sys_time::get_sys_time_in_secs();
@@ -58,11 +65,12 @@ pub async fn api_admin_meta_update(
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
-        trx.execute("INSERT OR REPLACE INTO meta_page (page_name, title, page_text, updated_date) VALUES (?, ?, ?, ?)", (page_name, title, text, time))?;
+        let revision_uuid = meta_pages::save_meta_page(&trx, page_name, &lang, title, text, time)?;
         trx.commit()?;
 
         Ok(Json(serde_json::json!({
             "status": "ok",
+            "revision_uuid": revision_uuid,
         })))
     }, ErrorReporting::Json).await
 }
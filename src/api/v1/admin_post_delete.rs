@@ -29,7 +29,7 @@ pub async fn api_admin_post_delete(
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
-        if let Err(_) = trx.query_row("SELECT id FROM post WHERE uuid = ?", [uuid], |row| row.get::<_, u32>(0)) {
+        if trx.query_row("SELECT id FROM post WHERE uuid = ?", [uuid], |row| row.get::<_, u32>(0)).is_err() {
             return Err(anyhow::anyhow!("Post not found"));
         }
 
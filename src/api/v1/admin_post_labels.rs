@@ -0,0 +1,57 @@
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{State, Query},
+    response::IntoResponse,
+    Json,
+};
+
+use serde::{Serialize, Deserialize};
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::crypto;
+use crate::labels;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MsgPostLabels {
+    /// Replaces the labels applied by admins. Labels applied by the authors are kept.
+    labels: Vec<String>,
+}
+
+pub async fn api_admin_post_labels(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+    Json(msg): Json<MsgPostLabels>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        let admin_token = state.primary_secret.derive_secret("admin_token");
+        let token = hex::decode(params.get("token").ok_or_else(|| anyhow::anyhow!("Missing token parameter"))?)?;
+
+        if !crypto::constant_time_eq(&admin_token, &token) {
+            return Err(anyhow::anyhow!("Invalid token"));
+        }
+
+        let uuid = params.get("uuid").ok_or_else(|| anyhow::anyhow!("Missing uuid parameter"))?;
+
+        labels::validate_labels(&msg.labels, None)?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let post_id: u32 = trx.query_row("SELECT id FROM post WHERE uuid = ?", [uuid], |row| row.get(0))
+            .map_err(|_| anyhow::anyhow!("Post not found"))?;
+
+        labels::set_post_labels(&trx, post_id, &msg.labels, true)?;
+        let post_labels = labels::get_post_labels(&trx, post_id)?;
+        trx.commit()?;
+
+        Ok(Json(serde_json::json!({
+            "status": "ok",
+            "labels": post_labels,
+        })))
+    }, ErrorReporting::Json).await
+}
@@ -10,6 +10,7 @@ use axum::{
 
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::icons;
 
 use crate::api::v1::types::ChannelSummary;
 
@@ -25,7 +26,7 @@ pub async fn api_author_channels(
         let trx = db_connection.transaction()?;
 
         let mut stmt = trx.prepare(
-            "SELECT channel.uuid, channel.handle, channel.name, channel.language_code FROM channel, channel_author, author WHERE channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = author.id AND author.is_deleted = 0 AND author.uuid = ? ORDER BY channel.created_date DESC LIMIT 1000"
+            &format!("SELECT channel.uuid, channel.handle, channel.name, channel.language_code, {channel_icon} FROM channel, channel_author, author WHERE channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = author.id AND author.is_deleted = 0 AND author.uuid = ? ORDER BY channel.created_date DESC LIMIT 1000", channel_icon = icons::channel_summary_icon_query("channel.id"))
         )?;
 
         let mut rows = stmt.query([author_uuid])?;
@@ -36,7 +37,8 @@ pub async fn api_author_channels(
             let handle: String = row.get(1)?;
             let name: String = row.get(2)?;
             let language_code: String = row.get(3)?;
-            channels.push(ChannelSummary::new(&channel_uuid, &handle, &name, &language_code));
+            let icon_sha256: Option<String> = row.get(4)?;
+            channels.push(ChannelSummary::new(&channel_uuid, &handle, &name, &language_code, icon_sha256.as_deref()));
         }
 
         Ok(Json(serde_json::json!(channels)))
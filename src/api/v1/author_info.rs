@@ -12,7 +12,13 @@ use axum::{
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
 
-use crate::api::v1::types::AuthorInfo;
+use crate::markdown;
+use crate::icons;
+
+use crate::api::v1::types::{
+    is_html_format_requested,
+    AuthorInfo,
+};
 
 
 pub async fn api_author_info(
@@ -21,18 +27,20 @@ pub async fn api_author_info(
 ) -> impl IntoResponse {
     result_into_response(async move {
         let author_uuid = params.get("uuid").ok_or_else(|| anyhow::anyhow!("Missing uuid parameter"))?;
+        let render_html = is_html_format_requested(params.get("format"))?;
         
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
-        let (name, created_date, description_text) = if let Ok(values) = trx.query_row(
-            "SELECT name, registered_date, description_text FROM author WHERE is_deleted = 0 AND uuid = ?",
+        let (author_id, name, created_date, description_text) = if let Ok(values) = trx.query_row(
+            "SELECT id, name, registered_date, description_text FROM author WHERE is_deleted = 0 AND uuid = ?",
             [&author_uuid],
             |row| {
-                let name: String = row.get(0)?;
-                let created_date: u64 = row.get(1)?;
-                let description_text: String = row.get(2)?;
-                Ok((name, created_date, description_text))
+                let author_id: u32 = row.get(0)?;
+                let name: String = row.get(1)?;
+                let created_date: u64 = row.get(2)?;
+                let description_text: String = row.get(3)?;
+                Ok((author_id, name, created_date, description_text))
             }
         ) {
             values
@@ -45,7 +53,12 @@ pub async fn api_author_info(
             ).into_response());
         };
 
-        let author = AuthorInfo::new(author_uuid, &name, created_date, &description_text);
+        let mut author = AuthorInfo::new(author_uuid, &name, created_date, &description_text);
+        author.set_icons(icons::get_author_icons(&trx, author_id)?);
+        if render_html {
+            author.set_rendered_html(&markdown::render_markdown(&description_text));
+        }
+
         Ok(Json(author).into_response())
     }, ErrorReporting::Json).await
 }
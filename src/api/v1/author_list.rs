@@ -10,31 +10,39 @@ use axum::{
 
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::pagination::{Cursor, PageParams};
+use crate::icons;
 
 use crate::api::v1::types::AuthorSummary;
 
 
 pub async fn api_author_list(
     State(state): State<Arc<AppState>>,
-    Query(_params): Query<HashMap<String, String>>,
+    Query(params): Query<HashMap<String, String>>,
 ) -> impl IntoResponse {
     result_into_response(async move {
+        let page = PageParams::<u32>::from_query(&params)?;
+
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
         let mut stmt = trx.prepare(
-            "SELECT uuid, name FROM author WHERE is_deleted = 0 ORDER BY registered_date DESC LIMIT 1000"
+            &format!("SELECT uuid, name, {author_icon}, id, registered_date FROM author WHERE is_deleted = 0 AND (?1 IS NULL OR registered_date < ?1 OR (registered_date = ?1 AND id < ?2)) ORDER BY registered_date DESC, id DESC LIMIT ?3", author_icon = icons::author_summary_icon_query("author.id"))
         )?;
 
-        let mut rows = stmt.query([])?;
+        let mut rows = stmt.query((page.cursor_key(), page.cursor_id(), page.query_limit()))?;
         let mut authors = Vec::new();
 
         while let Some(row) = rows.next()? {
             let author_uuid: String = row.get(0)?;
             let name: String = row.get(1)?;
-            authors.push(AuthorSummary::new(&author_uuid, &name));
+            let icon_sha256: Option<String> = row.get(2)?;
+            let author_id: u32 = row.get(3)?;
+            let registered_date: u64 = row.get(4)?;
+            let author = AuthorSummary::new(&author_uuid, &name, icon_sha256.as_deref());
+            authors.push((Cursor::new(registered_date, author_id), serde_json::json!(author)));
         }
 
-        Ok(Json(serde_json::json!(authors)))
+        Ok(Json(page.into_response(authors)))
     }, ErrorReporting::Json).await
 }
@@ -10,6 +10,10 @@ use axum::{
 
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::labels;
+use crate::listings;
+use crate::pagination::{Cursor, PageParams};
+use crate::icons;
 
 use crate::api::v1::types::ChannelSummary;
 
@@ -21,14 +25,19 @@ pub async fn api_author_posts(
     result_into_response(async move {
         let author_uuid = params.get("uuid").ok_or_else(|| anyhow::anyhow!("Missing uuid parameter"))?;
 
+        // posts with any of the labels, or with any label or content warning if `all`
+        let (exclude_all_labels, exclude_labels) = labels::parse_exclude_labels(params.get("exclude_labels"))?;
+
+        let page = PageParams::<u32>::from_query(&params)?;
+
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
         let mut stmt = trx.prepare(
-            "SELECT channel.uuid, channel.handle, channel.name, channel.language_code, post.uuid, revision.uuid, revision.created_date, revision.title FROM channel, post, revision, author WHERE channel.is_deleted = 0 AND post.is_deleted = 0 AND revision.is_deleted = 0 AND channel.id = post.channel_id AND post.id = revision.post_id AND revision.author_id = author.id AND author.is_deleted = 0 AND author.uuid = ? ORDER BY revision.created_date DESC LIMIT 1000"
+            &format!("SELECT channel.uuid, channel.handle, channel.name, channel.language_code, post.uuid, revision.uuid, revision.created_date, revision.title, post_slug.slug, {channel_icon}, COALESCE(post.language_code, channel.language_code), {post_labels}, post.content_warning, revision.id, post.published_date, post.updated_date FROM {post_tables} WHERE {post_conditions} AND author.uuid = ?1 AND {exclude_labels} AND (?4 IS NULL OR revision.created_date < ?4 OR (revision.created_date = ?4 AND revision.id < ?5)) ORDER BY revision.created_date DESC, revision.id DESC LIMIT ?6", channel_icon = icons::channel_summary_icon_query("channel.id"), post_tables = listings::POST_LISTING_TABLES, post_conditions = listings::POST_LISTING_CONDITIONS, post_labels = labels::POST_LABELS_COLUMN, exclude_labels = labels::exclude_labels_condition("?2", "?3"))
         )?;
 
-        let mut rows = stmt.query([author_uuid])?;
+        let mut rows = stmt.query((author_uuid, exclude_all_labels, &exclude_labels, page.cursor_key(), page.cursor_id(), page.query_limit()))?;
         let mut posts = Vec::new();
 
         while let Some(row) = rows.next()? {
@@ -41,17 +50,31 @@ pub async fn api_author_posts(
             let revision_uuid: String = row.get(5)?;
             let revision_date: u64 = row.get(6)?;
             let title: String = row.get(7)?;
+            let slug: Option<String> = row.get(8)?;
+            let icon_sha256: Option<String> = row.get(9)?;
+            let lang: String = row.get(10)?;
+            let post_labels: Option<String> = row.get(11)?;
+            let content_warning: Option<String> = row.get(12)?;
+            let revision_id: u32 = row.get(13)?;
+            let published_date: u64 = row.get(14)?;
+            let updated_date: u64 = row.get(15)?;
 
-            let channel = ChannelSummary::new(&channel_uuid, &handle, &name, &language_code);
-            posts.push(serde_json::json!({
+            let channel = ChannelSummary::new(&channel_uuid, &handle, &name, &language_code, icon_sha256.as_deref());
+            posts.push((Cursor::new(revision_date, revision_id), serde_json::json!({
                 "post_uuid": post_uuid,
+                "slug": slug,
                 "revision_uuid": revision_uuid, // this might not be latest revision
                 "revision_date": revision_date, // this might not be latest revision
+                "published_date": published_date,
+                "updated_date": updated_date,
                 "title": title, // this might not be latest revision
                 "channel": channel,
-            }));
+                "lang": lang,
+                "labels": labels::split_labels(post_labels),
+                "content_warning": content_warning,
+            })));
         }
 
-        Ok(Json(serde_json::json!(posts)))
+        Ok(Json(page.into_response(posts)))
     }, ErrorReporting::Json).await
 }
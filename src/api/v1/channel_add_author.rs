@@ -0,0 +1,102 @@
+
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+use monostate::MustBe;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::crypto::SignedMessage;
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::roles;
+
+use crate::api::v1::types::ChannelRole;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MsgChannelAddAuthor {
+    command: MustBe!("channel_add_author"),
+    uuid: String,
+    author_uuid: String,
+
+    /// Defaults to contributor.
+    #[serde(default)]
+    role: Option<String>,
+}
+
+pub async fn api_channel_add_author(
+    State(state): State<Arc<AppState>>,
+    Json(msg): Json<SignedMessage>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        // verify message
+        let public_key = msg.public_key()?.to_owned();
+        let msg = msg.verify()?;
+        let msg = serde_json::from_slice::<MsgChannelAddAuthor>(msg)?;
+
+        let role = match &msg.role {
+            Some(role) => role.parse::<ChannelRole>()?,
+            None => ChannelRole::Contributor,
+        };
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let author_id = trx.query_row(
+            "SELECT author.id FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+            [&public_key],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                Ok(author_id)
+            }
+        )?;
+
+        let channel_id = trx.query_row(
+            "SELECT channel.id FROM channel, channel_author WHERE channel.uuid = ? AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?",
+            (&msg.uuid, &author_id),
+            |row| {
+                let channel_id: u32 = row.get(0)?;
+                Ok(channel_id)
+            }
+        )?;
+
+        roles::check_channel_manageable(&trx, channel_id, author_id)?;
+
+        let new_author_id = trx.query_row(
+            "SELECT id FROM author WHERE uuid = ? AND is_deleted = 0",
+            [&msg.author_uuid],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                Ok(author_id)
+            }
+        ).map_err(|_| anyhow::anyhow!("Author not found"))?;
+
+        if new_author_id == author_id {
+            return Err(anyhow::anyhow!("Cannot add yourself"));
+        }
+
+        if trx.query_row(
+            "SELECT id FROM channel_author WHERE channel_id = ? AND author_id = ?",
+            (channel_id, new_author_id),
+            |row| row.get::<_, u32>(0),
+        ).is_ok() {
+            return Err(anyhow::anyhow!("Author is already in the channel"));
+        }
+
+        trx.execute(
+            "INSERT INTO channel_author (channel_id, author_id, role) VALUES (?, ?, ?)",
+            (channel_id, new_author_id, role.as_str()),
+        )?;
+
+        trx.commit()?;
+
+        Ok(Json(serde_json::json!({
+            "status": "ok",
+        })))
+    }, ErrorReporting::Json).await
+}
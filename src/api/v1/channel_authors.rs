@@ -10,8 +10,13 @@ use axum::{
 
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::icons;
 
-use crate::api::v1::types::AuthorSummary;
+use crate::api::v1::types::{
+    AuthorSummary,
+    ChannelRole,
+    ChannelAuthor,
+};
 
 
 pub async fn api_channel_authors(
@@ -25,7 +30,7 @@ pub async fn api_channel_authors(
         let trx = db_connection.transaction()?;
 
         let mut stmt = trx.prepare(
-            "SELECT author.uuid, author.name FROM channel, channel_author, author WHERE channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = author.id AND author.is_deleted = 0 AND channel.uuid = ? ORDER BY author.registered_date DESC LIMIT 1000"
+            &format!("SELECT author.uuid, author.name, {author_icon}, channel_author.role FROM channel, channel_author, author WHERE channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = author.id AND author.is_deleted = 0 AND channel.uuid = ? ORDER BY author.registered_date DESC LIMIT 1000", author_icon = icons::author_summary_icon_query("author.id"))
         )?;
 
         let mut rows = stmt.query([channel_uuid])?;
@@ -34,7 +39,10 @@ pub async fn api_channel_authors(
         while let Some(row) = rows.next()? {
             let author_uuid: String = row.get(0)?;
             let name: String = row.get(1)?;
-            authors.push(AuthorSummary::new(&author_uuid, &name));
+            let icon_sha256: Option<String> = row.get(2)?;
+            let role: String = row.get(3)?;
+            let author = AuthorSummary::new(&author_uuid, &name, icon_sha256.as_deref());
+            authors.push(ChannelAuthor::new(&author, role.parse::<ChannelRole>()?));
         }
 
         Ok(Json(serde_json::json!(authors)))
@@ -13,6 +13,7 @@ use axum::{
 use crate::crypto::SignedMessage;
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::roles;
 
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,7 +30,7 @@ pub async fn api_channel_delete(
         // verify message
         let public_key = msg.public_key()?.to_owned();
         let msg = msg.verify()?;
-        let msg = serde_json::from_slice::<MsgChannelDelete>(&msg)?;
+        let msg = serde_json::from_slice::<MsgChannelDelete>(msg)?;
 
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
@@ -52,6 +53,8 @@ pub async fn api_channel_delete(
             }
         )?;
 
+        roles::check_channel_manageable(&trx, channel_id, author_id)?;
+
         trx.execute(
             "UPDATE channel SET is_deleted = 1 WHERE id = ?",
             [&channel_id],
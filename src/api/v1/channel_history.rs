@@ -0,0 +1,89 @@
+
+use std::sync::Arc;
+use std::collections::HashMap;
+
+use hyper::StatusCode;
+use axum::{
+    extract::{State, Query},
+    response::IntoResponse,
+    Json,
+};
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::icons;
+
+use crate::api::v1::types::AuthorSummary;
+
+
+pub async fn api_channel_history(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        let channel_uuid = params.get("uuid").ok_or_else(|| anyhow::anyhow!("Missing uuid parameter"))?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let channel_id = if let Ok(channel_id) = trx.query_row(
+            "SELECT id FROM channel WHERE uuid = ? AND is_deleted = 0",
+            [channel_uuid],
+            |row| row.get::<_, u32>(0),
+        ) {
+            channel_id
+        } else {
+            return Ok((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({
+                    "status": "not found",
+                })),
+            ).into_response());
+        };
+
+        // deleted authors are shown as null
+        let mut stmt = trx.prepare(
+            &format!(
+                "
+                    SELECT channel_history.event, channel_history.created_date, author.uuid, author.name,
+                    {author_icon},
+                    target.uuid, target.name,
+                    {target_icon}
+                    FROM channel_history
+                    LEFT JOIN author ON channel_history.author_id = author.id AND author.is_deleted = 0
+                    LEFT JOIN author AS target ON channel_history.target_author_id = target.id AND target.is_deleted = 0
+                    WHERE channel_history.channel_id = ?
+                    ORDER BY channel_history.created_date DESC, channel_history.id DESC
+                    LIMIT 1000
+                ",
+                author_icon = icons::author_summary_icon_query("author.id"), target_icon = icons::author_summary_icon_query("target.id"),
+            )
+        )?;
+
+        let mut rows = stmt.query([channel_id])?;
+        let mut events = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let event: String = row.get(0)?;
+            let date: u64 = row.get(1)?;
+            let author_uuid: Option<String> = row.get(2)?;
+            let author_name: Option<String> = row.get(3)?;
+            let author_icon_sha256: Option<String> = row.get(4)?;
+            let target_uuid: Option<String> = row.get(5)?;
+            let target_name: Option<String> = row.get(6)?;
+            let target_icon_sha256: Option<String> = row.get(7)?;
+
+            let author = author_uuid.zip(author_name).map(|(uuid, name)| AuthorSummary::new(&uuid, &name, author_icon_sha256.as_deref()));
+            let target_author = target_uuid.zip(target_name).map(|(uuid, name)| AuthorSummary::new(&uuid, &name, target_icon_sha256.as_deref()));
+
+            events.push(serde_json::json!({
+                "event": event,
+                "date": date,
+                "author": author,
+                "target_author": target_author,
+            }));
+        }
+
+        Ok(Json(serde_json::json!(events)).into_response())
+    }, ErrorReporting::Json).await
+}
@@ -12,7 +12,13 @@ use axum::{
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
 
-use crate::api::v1::types::ChannelInfo;
+use crate::markdown;
+use crate::icons;
+
+use crate::api::v1::types::{
+    is_html_format_requested,
+    ChannelInfo,
+};
 
 
 enum QueryType {
@@ -27,6 +33,7 @@ pub async fn api_channel_info(
     result_into_response(async move {
         let maybe_channel_uuid = params.get("uuid");
         let maybe_channel_handle = params.get("handle");
+        let render_html = is_html_format_requested(params.get("format"))?;
 
         if maybe_channel_handle.is_none() && maybe_channel_uuid.is_none() {
             return Err(anyhow::anyhow!("Missing uuid or handle parameter"));
@@ -47,29 +54,35 @@ pub async fn api_channel_info(
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
-        let (name, created_date, description_text, channel_uuid, channel_handle, language_code) = if let Ok(values) = match query_type {
+        let (channel_id, name, created_date, description_text, channel_uuid, channel_handle, language_code, comments_enabled, license) = if let Ok(values) = match query_type {
             QueryType::ByUuid(channel_uuid) => trx.query_row(
-                "SELECT name, created_date, description_text, handle, language_code FROM channel WHERE is_deleted = 0 AND uuid = ?",
+                "SELECT id, name, created_date, description_text, handle, language_code, comments_enabled, license FROM channel WHERE is_deleted = 0 AND uuid = ?",
                 [channel_uuid.clone()],
                 |row| {
-                    let name: String = row.get(0)?;
-                    let created_date: u64 = row.get(1)?;
-                    let description_text: String = row.get(2)?;
-                    let channel_handle: String = row.get(3)?;
-                    let language_code: String = row.get(4)?;
-                    Ok((name, created_date, description_text, channel_uuid, channel_handle, language_code))
+                    let channel_id: u32 = row.get(0)?;
+                    let name: String = row.get(1)?;
+                    let created_date: u64 = row.get(2)?;
+                    let description_text: String = row.get(3)?;
+                    let channel_handle: String = row.get(4)?;
+                    let language_code: String = row.get(5)?;
+                    let comments_enabled: bool = row.get(6)?;
+                    let license: String = row.get(7)?;
+                    Ok((channel_id, name, created_date, description_text, channel_uuid, channel_handle, language_code, comments_enabled, license))
                 }
             ),
             QueryType::ByHandle(channel_handle) => trx.query_row(
-                "SELECT name, created_date, description_text, uuid, language_code FROM channel WHERE is_deleted = 0 AND handle = ?",
+                "SELECT id, name, created_date, description_text, uuid, language_code, comments_enabled, license FROM channel WHERE is_deleted = 0 AND handle = ?",
                 [channel_handle.clone()],
                 |row| {
-                    let name: String = row.get(0)?;
-                    let created_date: u64 = row.get(1)?;
-                    let description_text: String = row.get(2)?;
-                    let channel_uuid: String = row.get(3)?;
-                    let language_code: String = row.get(4)?;
-                    Ok((name, created_date, description_text, channel_uuid, channel_handle, language_code))
+                    let channel_id: u32 = row.get(0)?;
+                    let name: String = row.get(1)?;
+                    let created_date: u64 = row.get(2)?;
+                    let description_text: String = row.get(3)?;
+                    let channel_uuid: String = row.get(4)?;
+                    let language_code: String = row.get(5)?;
+                    let comments_enabled: bool = row.get(6)?;
+                    let license: String = row.get(7)?;
+                    Ok((channel_id, name, created_date, description_text, channel_uuid, channel_handle, language_code, comments_enabled, license))
                 }
             ),
         } {
@@ -83,7 +96,14 @@ pub async fn api_channel_info(
             ).into_response());
         };
 
-        let channel = ChannelInfo::new(&channel_uuid, &channel_handle, &name, created_date, &language_code, &description_text);
+        let mut channel = ChannelInfo::new(&channel_uuid, &channel_handle, &name, created_date, &language_code, &description_text);
+        channel.set_license(&license);
+        channel.set_comments_enabled(comments_enabled);
+        channel.set_icons(icons::get_channel_icons(&trx, channel_id)?);
+        if render_html {
+            channel.set_rendered_html(&markdown::render_markdown(&description_text));
+        }
+
         Ok(Json(channel).into_response())
     }, ErrorReporting::Json).await
 }
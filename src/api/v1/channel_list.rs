@@ -10,23 +10,27 @@ use axum::{
 
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::pagination::{Cursor, PageParams};
+use crate::icons;
 
 use crate::api::v1::types::ChannelSummary;
 
 
 pub async fn api_channel_list(
     State(state): State<Arc<AppState>>,
-    Query(_params): Query<HashMap<String, String>>,
+    Query(params): Query<HashMap<String, String>>,
 ) -> impl IntoResponse {
     result_into_response(async move {
+        let page = PageParams::<u32>::from_query(&params)?;
+
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
         let mut stmt = trx.prepare(
-            "SELECT uuid, handle, name, language_code FROM channel WHERE is_deleted = 0 ORDER BY created_date DESC LIMIT 1000"
+            &format!("SELECT uuid, handle, name, language_code, {channel_icon}, id, created_date FROM channel WHERE is_deleted = 0 AND (?1 IS NULL OR created_date < ?1 OR (created_date = ?1 AND id < ?2)) ORDER BY created_date DESC, id DESC LIMIT ?3", channel_icon = icons::channel_summary_icon_query("channel.id"))
         )?;
 
-        let mut rows = stmt.query([])?;
+        let mut rows = stmt.query((page.cursor_key(), page.cursor_id(), page.query_limit()))?;
         let mut channels = Vec::new();
 
         while let Some(row) = rows.next()? {
@@ -34,9 +38,13 @@ pub async fn api_channel_list(
             let handle: String = row.get(1)?;
             let name: String = row.get(2)?;
             let language_code: String = row.get(3)?;
-            channels.push(ChannelSummary::new(&channel_uuid, &handle, &name, &language_code));
+            let icon_sha256: Option<String> = row.get(4)?;
+            let channel_id: u32 = row.get(5)?;
+            let created_date: u64 = row.get(6)?;
+            let channel = ChannelSummary::new(&channel_uuid, &handle, &name, &language_code, icon_sha256.as_deref());
+            channels.push((Cursor::new(created_date, channel_id), serde_json::json!(channel)));
         }
 
-        Ok(Json(serde_json::json!(channels)))
+        Ok(Json(page.into_response(channels)))
     }, ErrorReporting::Json).await
 }
@@ -17,9 +17,11 @@ use crate::sys_time;
 use crate::limits;
 
 use crate::api::v1::types::{
-    validate_language_code,
+    normalize_language_code,
     validate_channel_handle,
+    validate_license,
     ChannelInfo,
+    DEFAULT_LICENSE,
 };
 
 
@@ -29,6 +31,9 @@ struct MsgChannelNew {
     handle: String,
     name: String,
     lang: String,
+
+    #[serde(default)]
+    license: Option<String>,
 }
 
 pub async fn api_channel_new(
@@ -39,11 +44,14 @@ pub async fn api_channel_new(
         // verify message
         let public_key = msg.public_key()?.to_owned();
         let msg = msg.verify()?;
-        let msg = serde_json::from_slice::<MsgChannelNew>(&msg)?;
+        let msg = serde_json::from_slice::<MsgChannelNew>(msg)?;
 
-        validate_language_code(&msg.lang)?;
+        let lang = normalize_language_code(&msg.lang)?;
         validate_channel_handle(&msg.handle)?;
 
+        let license = msg.license.as_deref().unwrap_or(DEFAULT_LICENSE);
+        validate_license(license)?;
+
         if msg.name.len() > limits::MAX_ITEM_NAME_SIZE {
             return Err(anyhow::anyhow!("Name is too long"));
         }
@@ -63,8 +71,8 @@ pub async fn api_channel_new(
         let uuid = uuid::Uuid::new_v4().to_string();
         let created_date = sys_time::get_sys_time_in_secs();
         trx.execute(
-            "INSERT INTO channel (uuid, handle, name, created_date, language_code) VALUES (?, ?, ?, ?, ?)",
-            (&uuid, &msg.handle, &msg.name, created_date, &msg.lang),
+            "INSERT INTO channel (uuid, handle, name, created_date, language_code, license) VALUES (?, ?, ?, ?, ?, ?)",
+            (&uuid, &msg.handle, &msg.name, created_date, &lang, license),
         )?;
 
         let channel_id = trx.query_row(
@@ -77,13 +85,14 @@ pub async fn api_channel_new(
         )?;
 
         trx.execute(
-            "INSERT INTO channel_author (channel_id, author_id) VALUES (?, ?)",
+            "INSERT INTO channel_author (channel_id, author_id, role) VALUES (?, ?, 'owner')",
             (channel_id, author_id),
         )?;
         
         trx.commit()?;
 
-        let channel = ChannelInfo::new(&uuid, &msg.handle, &msg.name, created_date, &msg.lang, "");
+        let mut channel = ChannelInfo::new(&uuid, &msg.handle, &msg.name, created_date, &lang, "");
+        channel.set_license(license);
 
         Ok(Json(channel))
     }, ErrorReporting::Json).await
@@ -0,0 +1,91 @@
+
+use std::sync::Arc;
+use std::collections::HashMap;
+
+use axum::{
+    extract::{State, Query},
+    response::IntoResponse,
+    Json,
+};
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::labels;
+use crate::listings;
+use crate::icons;
+
+use crate::api::v1::types::AuthorSummary;
+
+
+pub async fn api_channel_pinned(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        let channel_uuid = params.get("uuid").ok_or_else(|| anyhow::anyhow!("Missing uuid parameter"))?;
+
+        // posts with any of the labels, or with any label or content warning if `all`
+        let (exclude_all_labels, exclude_labels) = labels::parse_exclude_labels(params.get("exclude_labels"))?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let mut stmt = trx.prepare(
+            &format!(
+                "
+                    SELECT post.uuid, revision.uuid, MAX(revision.created_date), revision.title, author.uuid, author.name, post_slug.slug,
+                    {author_icon},
+                    COALESCE(post.language_code, channel.language_code),
+                    {post_labels}, post.content_warning, post.published_date, post.updated_date
+                    FROM {post_tables}, channel_pin
+                    WHERE {post_conditions}
+                    AND channel_pin.post_id = post.id AND channel_pin.channel_id = channel.id
+                    AND channel.uuid = ?1
+                    AND {exclude_labels}
+                    GROUP BY post.id
+                    ORDER BY channel_pin.position ASC
+                ",
+                author_icon = icons::author_summary_icon_query("author.id"),
+                post_tables = listings::POST_LISTING_TABLES,
+                post_conditions = listings::POST_LISTING_CONDITIONS,
+                post_labels = labels::POST_LABELS_COLUMN,
+                exclude_labels = labels::exclude_labels_condition("?2", "?3"),
+            )
+        )?;
+
+        let mut rows = stmt.query((channel_uuid, exclude_all_labels, &exclude_labels))?;
+        let mut posts = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let post_uuid: String = row.get(0)?;
+            let revision_uuid: String = row.get(1)?;
+            let revision_date: u64 = row.get(2)?;
+            let title: String = row.get(3)?;
+            let author_uuid: String = row.get(4)?;
+            let author_name: String = row.get(5)?;
+            let slug: Option<String> = row.get(6)?;
+            let author_icon_sha256: Option<String> = row.get(7)?;
+            let lang: String = row.get(8)?;
+            let post_labels: Option<String> = row.get(9)?;
+            let content_warning: Option<String> = row.get(10)?;
+            let published_date: u64 = row.get(11)?;
+            let updated_date: u64 = row.get(12)?;
+
+            posts.push(serde_json::json!({
+                "post_uuid": post_uuid,
+                "slug": slug,
+                "revision_uuid": revision_uuid,
+                "revision_date": revision_date,
+                "published_date": published_date,
+                "updated_date": updated_date,
+                "title": title,
+                "author": AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref()),
+                "lang": lang,
+                "labels": labels::split_labels(post_labels),
+                "content_warning": content_warning,
+            }));
+        }
+
+        Ok(Json(serde_json::json!(posts)))
+    }, ErrorReporting::Json).await
+}
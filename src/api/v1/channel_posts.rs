@@ -10,6 +10,10 @@ use axum::{
 
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::labels;
+use crate::listings;
+use crate::pagination::{Cursor, PageParams};
+use crate::icons;
 
 use crate::api::v1::types::AuthorSummary;
 
@@ -21,23 +25,40 @@ pub async fn api_channel_posts(
     result_into_response(async move {
         let channel_uuid = params.get("uuid").ok_or_else(|| anyhow::anyhow!("Missing uuid parameter"))?;
 
+        // posts with any of the labels, or with any label or content warning if `all`
+        let (exclude_all_labels, exclude_labels) = labels::parse_exclude_labels(params.get("exclude_labels"))?;
+
+        let page = PageParams::<u32>::from_query(&params)?;
+
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
         let mut stmt = trx.prepare(
-            "
-                SELECT post.uuid, revision.uuid, revision.created_date, revision.title, author.uuid, author.name
-                FROM channel, post, revision, author
-                WHERE channel.is_deleted = 0 AND post.is_deleted = 0 AND revision.is_deleted = 0 AND author.is_deleted = 0 
-                AND channel.id = post.channel_id AND post.id = revision.post_id AND revision.author_id = author.id
-                AND channel.uuid = ?
-                GROUP BY post.id
-                ORDER BY revision.created_date
-                DESC LIMIT 1000
-            "
+            &format!(
+                "
+                    SELECT post.uuid, revision.uuid, MAX(revision.created_date), revision.title, author.uuid, author.name, post_slug.slug,
+                    {author_icon},
+                    COALESCE(post.language_code, channel.language_code),
+                    EXISTS (SELECT 1 FROM channel_pin WHERE channel_pin.post_id = post.id),
+                    {post_labels}, post.content_warning, post.id, post.published_date, post.updated_date
+                    FROM {post_tables}
+                    WHERE {post_conditions}
+                    AND channel.uuid = ?1
+                    AND {exclude_labels}
+                    GROUP BY post.id
+                    HAVING ?4 IS NULL OR post.updated_date < ?4 OR (post.updated_date = ?4 AND post.id < ?5)
+                    ORDER BY post.updated_date DESC, post.id DESC
+                    LIMIT ?6
+                ",
+                author_icon = icons::author_summary_icon_query("author.id"),
+                post_tables = listings::POST_LISTING_TABLES,
+                post_conditions = listings::POST_LISTING_CONDITIONS,
+                post_labels = labels::POST_LABELS_COLUMN,
+                exclude_labels = labels::exclude_labels_condition("?2", "?3"),
+            )
         )?;
 
-        let mut rows = stmt.query([channel_uuid])?;
+        let mut rows = stmt.query((channel_uuid, exclude_all_labels, &exclude_labels, page.cursor_key(), page.cursor_id(), page.query_limit()))?;
         let mut posts = Vec::new();
 
         while let Some(row) = rows.next()? {
@@ -47,16 +68,32 @@ pub async fn api_channel_posts(
             let title: String = row.get(3)?;
             let author_uuid: String = row.get(4)?;
             let author_name: String = row.get(5)?;
+            let slug: Option<String> = row.get(6)?;
+            let author_icon_sha256: Option<String> = row.get(7)?;
+            let lang: String = row.get(8)?;
+            let pinned: bool = row.get(9)?;
+            let post_labels: Option<String> = row.get(10)?;
+            let content_warning: Option<String> = row.get(11)?;
+            let post_id: u32 = row.get(12)?;
+            let published_date: u64 = row.get(13)?;
+            let updated_date: u64 = row.get(14)?;
 
-            posts.push(serde_json::json!({
+            posts.push((Cursor::new(updated_date, post_id), serde_json::json!({
                 "post_uuid": post_uuid,
+                "slug": slug,
                 "revision_uuid": revision_uuid,
                 "revision_date": revision_date,
+                "published_date": published_date,
+                "updated_date": updated_date,
                 "title": title,
-                "author": AuthorSummary::new(&author_uuid, &author_name),
-            }));
+                "author": AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref()),
+                "lang": lang,
+                "pinned": pinned,
+                "labels": labels::split_labels(post_labels),
+                "content_warning": content_warning,
+            })));
         }
 
-        Ok(Json(serde_json::json!(posts)))
+        Ok(Json(page.into_response(posts)))
     }, ErrorReporting::Json).await
 }
@@ -0,0 +1,90 @@
+
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+use monostate::MustBe;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::crypto::SignedMessage;
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::roles;
+
+use crate::api::v1::types::ChannelRole;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MsgChannelSetAuthorRole {
+    command: MustBe!("channel_set_author_role"),
+    uuid: String,
+    author_uuid: String,
+    role: String,
+}
+
+pub async fn api_channel_set_author_role(
+    State(state): State<Arc<AppState>>,
+    Json(msg): Json<SignedMessage>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        // verify message
+        let public_key = msg.public_key()?.to_owned();
+        let msg = msg.verify()?;
+        let msg = serde_json::from_slice::<MsgChannelSetAuthorRole>(msg)?;
+
+        let role = msg.role.parse::<ChannelRole>()?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let author_id = trx.query_row(
+            "SELECT author.id FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+            [&public_key],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                Ok(author_id)
+            }
+        )?;
+
+        let channel_id = trx.query_row(
+            "SELECT channel.id FROM channel, channel_author WHERE channel.uuid = ? AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?",
+            (&msg.uuid, &author_id),
+            |row| {
+                let channel_id: u32 = row.get(0)?;
+                Ok(channel_id)
+            }
+        )?;
+
+        roles::check_channel_manageable(&trx, channel_id, author_id)?;
+
+        let (target_author_id, current_role) = trx.query_row(
+            "SELECT author.id, channel_author.role FROM author, channel_author WHERE author.uuid = ? AND author.is_deleted = 0 AND author.id = channel_author.author_id AND channel_author.channel_id = ?",
+            (&msg.author_uuid, channel_id),
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                let role: String = row.get(1)?;
+                Ok((author_id, role))
+            }
+        ).map_err(|_| anyhow::anyhow!("Author is not in the channel"))?;
+
+        // owners may step down only if another owner remains
+        if current_role.parse::<ChannelRole>()? == ChannelRole::Owner && role != ChannelRole::Owner && roles::count_channel_owners(&trx, channel_id)? <= 1 {
+            return Err(anyhow::anyhow!("Cannot demote the last owner of a channel"));
+        }
+
+        trx.execute(
+            "UPDATE channel_author SET role = ? WHERE channel_id = ? AND author_id = ?",
+            (role.as_str(), channel_id, target_author_id),
+        )?;
+
+        trx.commit()?;
+
+        Ok(Json(serde_json::json!({
+            "status": "ok",
+        })))
+    }, ErrorReporting::Json).await
+}
@@ -0,0 +1,106 @@
+
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+use monostate::MustBe;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::crypto::SignedMessage;
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::sys_time;
+use crate::roles;
+
+use crate::api::v1::types::ChannelRole;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MsgChannelTransferAccept {
+    command: MustBe!("channel_transfer_accept"),
+    offer_uuid: String,
+}
+
+pub async fn api_channel_transfer_accept(
+    State(state): State<Arc<AppState>>,
+    Json(msg): Json<SignedMessage>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        // verify message
+        let public_key = msg.public_key()?.to_owned();
+        let msg = msg.verify()?;
+        let msg = serde_json::from_slice::<MsgChannelTransferAccept>(msg)?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let author_id = trx.query_row(
+            "SELECT author.id FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+            [&public_key],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                Ok(author_id)
+            }
+        )?;
+
+        let now = sys_time::get_sys_time_in_secs();
+        let (offer_id, channel_id, from_author_id) = trx.query_row(
+            "
+                SELECT channel_transfer.id, channel.id, channel_transfer.from_author_id FROM channel_transfer, channel
+                WHERE channel_transfer.uuid = ? AND channel_transfer.to_author_id = ? AND channel_transfer.expires_date > ?
+                AND channel_transfer.channel_id = channel.id AND channel.is_deleted = 0
+            ",
+            (&msg.offer_uuid, author_id, now),
+            |row| {
+                let offer_id: u32 = row.get(0)?;
+                let channel_id: u32 = row.get(1)?;
+                let from_author_id: u32 = row.get(2)?;
+                Ok((offer_id, channel_id, from_author_id))
+            }
+        ).map_err(|_| anyhow::anyhow!("Offer not found or expired"))?;
+
+        // the offer is void if the offering author is no longer an owner
+        roles::check_channel_manageable(&trx, channel_id, from_author_id)?;
+
+        match roles::get_channel_role(&trx, channel_id, author_id) {
+            Ok(_) => {
+                trx.execute(
+                    "UPDATE channel_author SET role = ? WHERE channel_id = ? AND author_id = ?",
+                    (ChannelRole::Owner.as_str(), channel_id, author_id),
+                )?;
+            },
+            Err(_) => {
+                trx.execute(
+                    "INSERT INTO channel_author (channel_id, author_id, role) VALUES (?, ?, ?)",
+                    (channel_id, author_id, ChannelRole::Owner.as_str()),
+                )?;
+            },
+        }
+
+        // the previous owner stays in the channel as an editor
+        trx.execute(
+            "UPDATE channel_author SET role = ? WHERE channel_id = ? AND author_id = ?",
+            (ChannelRole::Editor.as_str(), channel_id, from_author_id),
+        )?;
+
+        trx.execute(
+            "DELETE FROM channel_transfer WHERE id = ?",
+            [offer_id],
+        )?;
+
+        trx.execute(
+            "INSERT INTO channel_history (channel_id, event, author_id, target_author_id, created_date) VALUES (?, 'ownership_transfer', ?, ?, ?)",
+            (channel_id, from_author_id, author_id, now),
+        )?;
+
+        trx.commit()?;
+
+        Ok(Json(serde_json::json!({
+            "status": "ok",
+        })))
+    }, ErrorReporting::Json).await
+}
@@ -0,0 +1,98 @@
+
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+use monostate::MustBe;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::crypto::SignedMessage;
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::sys_time;
+use crate::roles;
+
+
+/// Offers not accepted within this period expire.
+static CHANNEL_TRANSFER_OFFER_TTL_SECS: u64 = 7 * 86400;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MsgChannelTransferOffer {
+    command: MustBe!("channel_transfer_offer"),
+    uuid: String,
+    recipient_uuid: String,
+}
+
+pub async fn api_channel_transfer_offer(
+    State(state): State<Arc<AppState>>,
+    Json(msg): Json<SignedMessage>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        // verify message
+        let public_key = msg.public_key()?.to_owned();
+        let msg = msg.verify()?;
+        let msg = serde_json::from_slice::<MsgChannelTransferOffer>(msg)?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let author_id = trx.query_row(
+            "SELECT author.id FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+            [&public_key],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                Ok(author_id)
+            }
+        )?;
+
+        let channel_id = trx.query_row(
+            "SELECT channel.id FROM channel, channel_author WHERE channel.uuid = ? AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?",
+            (&msg.uuid, &author_id),
+            |row| {
+                let channel_id: u32 = row.get(0)?;
+                Ok(channel_id)
+            }
+        )?;
+
+        roles::check_channel_manageable(&trx, channel_id, author_id)?;
+
+        let recipient_id = trx.query_row(
+            "SELECT id FROM author WHERE uuid = ? AND is_deleted = 0",
+            [&msg.recipient_uuid],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                Ok(author_id)
+            }
+        ).map_err(|_| anyhow::anyhow!("Author not found"))?;
+
+        if recipient_id == author_id {
+            return Err(anyhow::anyhow!("Cannot transfer a channel to yourself"));
+        }
+
+        // a new offer replaces any pending offer for the channel
+        trx.execute(
+            "DELETE FROM channel_transfer WHERE channel_id = ?",
+            [channel_id],
+        )?;
+
+        let offer_uuid = uuid::Uuid::new_v4().to_string();
+        let created_date = sys_time::get_sys_time_in_secs();
+        let expires_date = created_date + CHANNEL_TRANSFER_OFFER_TTL_SECS;
+        trx.execute(
+            "INSERT INTO channel_transfer (uuid, channel_id, from_author_id, to_author_id, created_date, expires_date) VALUES (?, ?, ?, ?, ?, ?)",
+            (&offer_uuid, channel_id, author_id, recipient_id, created_date, expires_date),
+        )?;
+
+        trx.commit()?;
+
+        Ok(Json(serde_json::json!({
+            "status": "ok",
+            "offer_uuid": offer_uuid,
+            "expires_date": expires_date,
+        })))
+    }, ErrorReporting::Json).await
+}
@@ -12,12 +12,15 @@ use axum::{
 
 use crate::crypto::SignedMessage;
 use crate::limits;
+use crate::icons;
+use crate::roles;
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
 
 use crate::api::v1::types::{
-    validate_language_code,
+    normalize_language_code,
     validate_channel_handle,
+    validate_license,
     ChannelInfo,
 };
 
@@ -30,6 +33,18 @@ struct MsgChannelUpdate {
     name: String,
     lang: String,
     description_text: String,
+
+    /// The license is left unchanged if omitted.
+    #[serde(default)]
+    license: Option<String>,
+
+    /// Comments are left unchanged if omitted.
+    #[serde(default)]
+    comments_enabled: Option<bool>,
+
+    /// SHA-256 hash of an uploaded image. Empty string removes the icon.
+    #[serde(default)]
+    icon_sha256: Option<String>,
 }
 
 pub async fn api_channel_update(
@@ -40,11 +55,15 @@ pub async fn api_channel_update(
         // verify message
         let public_key = msg.public_key()?.to_owned();
         let msg = msg.verify()?;
-        let msg = serde_json::from_slice::<MsgChannelUpdate>(&msg)?;
+        let msg = serde_json::from_slice::<MsgChannelUpdate>(msg)?;
 
-        validate_language_code(&msg.lang)?;
+        let lang = normalize_language_code(&msg.lang)?;
         validate_channel_handle(&msg.handle)?;
 
+        if let Some(license) = &msg.license {
+            validate_license(license)?;
+        }
+
         if msg.name.len() > limits::MAX_ITEM_NAME_SIZE {
             return Err(anyhow::anyhow!("Name is too long"));
         }
@@ -53,6 +72,27 @@ pub async fn api_channel_update(
             return Err(anyhow::anyhow!("Description is too long"));
         }
 
+        // only registered authors get their images decoded
+        {
+            let mut db_connection = state.db_connection.lock().unwrap();
+            let trx = db_connection.transaction()?;
+            trx.query_row(
+                "SELECT author.id FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+                [&public_key],
+                |_| Ok(()),
+            )?;
+        }
+
+        // images are processed without holding the database lock, on a blocking thread
+        let icons = match msg.icon_sha256.as_deref() {
+            Some(icon_sha256) if !icon_sha256.is_empty() => {
+                let media_store = state.media_store.clone();
+                let icon_sha256 = icon_sha256.to_string();
+                Some(tokio::task::spawn_blocking(move || icons::load_and_render_icons(&media_store, &icon_sha256)).await??)
+            },
+            _ => None,
+        };
+
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
@@ -65,24 +105,48 @@ pub async fn api_channel_update(
             }
         )?;
 
-        let (channel_id, created_date) = trx.query_row(
-            "SELECT channel.id, channel.created_date FROM channel, channel_author WHERE channel.uuid = ? AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?",
+        let (channel_id, created_date, comments_enabled, license) = trx.query_row(
+            "SELECT channel.id, channel.created_date, channel.comments_enabled, channel.license FROM channel, channel_author WHERE channel.uuid = ? AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?",
             (&msg.uuid, &author_id),
             |row| {
                 let channel_id: u32 = row.get(0)?;
                 let created_date: u64 = row.get(1)?;
-                Ok((channel_id, created_date))
+                let comments_enabled: bool = row.get(2)?;
+                let license: String = row.get(3)?;
+                Ok((channel_id, created_date, comments_enabled, license))
             }
         )?;
 
+        roles::check_channel_manageable(&trx, channel_id, author_id)?;
+
+        let comments_enabled = msg.comments_enabled.unwrap_or(comments_enabled);
+        let license = msg.license.unwrap_or(license);
         trx.execute(
-            "UPDATE channel SET handle = ?, name = ?, language_code = ?, description_text = ? WHERE id = ?",
-            (&msg.handle, &msg.name, &msg.lang, &msg.description_text, &channel_id),
+            "UPDATE channel SET handle = ?, name = ?, language_code = ?, description_text = ?, comments_enabled = ?, license = ? WHERE id = ?",
+            (&msg.handle, &msg.name, &lang, &msg.description_text, comments_enabled, &license, &channel_id),
         )?;
 
+        match (msg.icon_sha256.as_deref(), icons) {
+            (Some(icon_sha256), Some(icons)) => {
+                if trx.query_row("SELECT id FROM media WHERE sha256 = ?", [icon_sha256], |_| Ok(())).is_err() {
+                    return Err(anyhow::anyhow!("Icon file not found"));
+                }
+                let media_ids = icons::store_icons(&trx, &state.media_store, author_id, &icons)?;
+                icons::set_channel_icons(&trx, channel_id, &media_ids)?;
+            },
+            (Some(_), None) => {
+                icons::set_channel_icons(&trx, channel_id, &[])?;
+            },
+            _ => {},
+        }
+
+        let channel_icons = icons::get_channel_icons(&trx, channel_id)?;
         trx.commit()?;
 
-        let channel = ChannelInfo::new(&msg.uuid, &msg.handle, &msg.name, created_date, &msg.lang, &msg.description_text);
+        let mut channel = ChannelInfo::new(&msg.uuid, &msg.handle, &msg.name, created_date, &lang, &msg.description_text);
+        channel.set_license(&license);
+        channel.set_comments_enabled(comments_enabled);
+        channel.set_icons(channel_icons);
 
         Ok(Json(channel))
     }, ErrorReporting::Json).await
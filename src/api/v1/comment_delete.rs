@@ -0,0 +1,71 @@
+
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+use monostate::MustBe;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::crypto::SignedMessage;
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MsgCommentDelete {
+    command: MustBe!("comment_delete"),
+    uuid: String,
+}
+
+pub async fn api_comment_delete(
+    State(state): State<Arc<AppState>>,
+    Json(msg): Json<SignedMessage>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        // verify message
+        let public_key = msg.public_key()?.to_owned();
+        let msg = msg.verify()?;
+        let msg = serde_json::from_slice::<MsgCommentDelete>(msg)?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let author_id = trx.query_row(
+            "SELECT author.id FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+            [&public_key],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                Ok(author_id)
+            }
+        )?;
+
+        // comments can be deleted by the commenter and by the owners and editors of the channel
+        let comment_id = trx.query_row(
+            "
+                SELECT comment.id FROM comment, post
+                WHERE comment.uuid = ? AND comment.is_deleted = 0 AND comment.post_id = post.id
+                AND (comment.author_id = ? OR EXISTS (SELECT 1 FROM channel_author WHERE channel_author.channel_id = post.channel_id AND channel_author.author_id = ? AND channel_author.role IN ('owner', 'editor')))
+            ",
+            (&msg.uuid, author_id, author_id),
+            |row| {
+                let comment_id: u32 = row.get(0)?;
+                Ok(comment_id)
+            }
+        )?;
+
+        trx.execute(
+            "UPDATE comment SET is_deleted = 1 WHERE id = ?",
+            (&comment_id,),
+        )?;
+
+        trx.commit()?;
+
+        Ok(Json(serde_json::json!({
+            "status": "ok",
+        })))
+    }, ErrorReporting::Json).await
+}
@@ -0,0 +1,111 @@
+
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+use monostate::MustBe;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::crypto::SignedMessage;
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::sys_time;
+use crate::limits;
+use crate::icons;
+
+use crate::api::v1::types::{
+    AuthorSummary,
+    CommentInfo,
+};
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MsgCommentNew {
+    command: MustBe!("comment_new"),
+    post_uuid: String,
+
+    /// Replies to this comment if present.
+    #[serde(default)]
+    parent_uuid: Option<String>,
+
+    text: String,
+}
+
+pub async fn api_comment_new(
+    State(state): State<Arc<AppState>>,
+    Json(msg): Json<SignedMessage>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        // verify message
+        let public_key = msg.public_key()?.to_owned();
+        let msg = msg.verify()?;
+        let msg = serde_json::from_slice::<MsgCommentNew>(msg)?;
+
+        if msg.text.is_empty() {
+            return Err(anyhow::anyhow!("Comment text is empty"));
+        }
+
+        if msg.text.len() > limits::MAX_COMMENT_TEXT_SIZE {
+            return Err(anyhow::anyhow!("Comment text is too long"));
+        }
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let (author_id, author_uuid, author_name, author_icon_sha256) = trx.query_row(
+            &format!("SELECT author.id, author.uuid, author.name, {author_icon} FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id", author_icon = icons::author_summary_icon_query("author.id")),
+            [&public_key],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                let author_uuid: String = row.get(1)?;
+                let author_name: String = row.get(2)?;
+                let author_icon_sha256: Option<String> = row.get(3)?;
+                Ok((author_id, author_uuid, author_name, author_icon_sha256))
+            }
+        )?;
+
+        // private posts can only be commented on by the authors of the channel
+        let (post_id, comments_enabled) = trx.query_row(
+            "SELECT post.id, channel.comments_enabled FROM post, channel WHERE post.uuid = ? AND post.is_deleted = 0 AND post.channel_id = channel.id AND channel.is_deleted = 0 AND (post.visibility != 'private' OR EXISTS (SELECT 1 FROM channel_author WHERE channel_author.channel_id = channel.id AND channel_author.author_id = ?))",
+            (&msg.post_uuid, author_id),
+            |row| {
+                let post_id: u32 = row.get(0)?;
+                let comments_enabled: bool = row.get(1)?;
+                Ok((post_id, comments_enabled))
+            }
+        ).map_err(|_| anyhow::anyhow!("Post not found"))?;
+
+        if !comments_enabled {
+            return Err(anyhow::anyhow!("Comments are disabled for this channel"));
+        }
+
+        let parent_id = if let Some(parent_uuid) = &msg.parent_uuid {
+            let parent_id = trx.query_row(
+                "SELECT id FROM comment WHERE uuid = ? AND post_id = ? AND is_deleted = 0",
+                (parent_uuid, post_id),
+                |row| row.get::<_, u32>(0),
+            ).map_err(|_| anyhow::anyhow!("Parent comment not found"))?;
+            Some(parent_id)
+        } else {
+            None
+        };
+
+        let comment_uuid = uuid::Uuid::new_v4().to_string();
+        let created_date = sys_time::get_sys_time_in_secs();
+        trx.execute(
+            "INSERT INTO comment (uuid, post_id, parent_id, author_id, created_date, comment_text) VALUES (?, ?, ?, ?, ?, ?)",
+            (&comment_uuid, post_id, parent_id, author_id, created_date, &msg.text),
+        )?;
+
+        trx.commit()?;
+
+        let author = AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref());
+        let comment = CommentInfo::new(&comment_uuid, &msg.post_uuid, msg.parent_uuid.as_deref(), &author, created_date, &msg.text);
+
+        Ok(Json(comment))
+    }, ErrorReporting::Json).await
+}
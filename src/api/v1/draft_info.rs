@@ -0,0 +1,63 @@
+
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+use monostate::MustBe;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::crypto::SignedMessage;
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::roles;
+use crate::drafts;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MsgDraftInfo {
+    command: MustBe!("draft_info"),
+    uuid: String,
+}
+
+pub async fn api_draft_info(
+    State(state): State<Arc<AppState>>,
+    Json(msg): Json<SignedMessage>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        // verify message
+        let public_key = msg.public_key()?.to_owned();
+        let msg = msg.verify()?;
+        let msg = serde_json::from_slice::<MsgDraftInfo>(msg)?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let author_id = trx.query_row(
+            "SELECT author.id FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+            [&public_key],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                Ok(author_id)
+            }
+        )?;
+
+        let draft_id = trx.query_row(
+            "SELECT draft.id FROM draft, channel, channel_author WHERE draft.uuid = ? AND draft.channel_id = channel.id AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?",
+            (&msg.uuid, &author_id),
+            |row| {
+                let draft_id: u32 = row.get(0)?;
+                Ok(draft_id)
+            }
+        )?;
+
+        roles::check_draft_editable(&trx, draft_id, author_id)?;
+
+        let draft = drafts::get_draft_info(&trx, draft_id)?;
+
+        Ok(Json(draft))
+    }, ErrorReporting::Json).await
+}
@@ -0,0 +1,76 @@
+
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+use monostate::MustBe;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::crypto::SignedMessage;
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::drafts;
+
+
+/// Lists the drafts written by self, or all drafts of a channel if `channel_uuid` is given.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MsgDraftList {
+    command: MustBe!("draft_list"),
+    channel_uuid: Option<String>,
+}
+
+pub async fn api_draft_list(
+    State(state): State<Arc<AppState>>,
+    Json(msg): Json<SignedMessage>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        // verify message
+        let public_key = msg.public_key()?.to_owned();
+        let msg = msg.verify()?;
+        let msg = serde_json::from_slice::<MsgDraftList>(msg)?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let author_id = trx.query_row(
+            "SELECT author.id FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+            [&public_key],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                Ok(author_id)
+            }
+        )?;
+
+        let mut draft_ids = Vec::new();
+        if let Some(channel_uuid) = &msg.channel_uuid {
+            let mut stmt = trx.prepare(
+                "SELECT draft.id FROM draft, channel, channel_author WHERE channel.uuid = ? AND draft.channel_id = channel.id AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ? AND (draft.author_id = channel_author.author_id OR channel_author.role IN ('owner', 'editor')) ORDER BY draft.updated_date DESC LIMIT 1000"
+            )?;
+            let mut rows = stmt.query((channel_uuid, &author_id))?;
+            while let Some(row) = rows.next()? {
+                let draft_id: u32 = row.get(0)?;
+                draft_ids.push(draft_id);
+            }
+        } else {
+            let mut stmt = trx.prepare(
+                "SELECT draft.id FROM draft, channel, channel_author WHERE draft.author_id = ? AND draft.channel_id = channel.id AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = draft.author_id ORDER BY draft.updated_date DESC LIMIT 1000"
+            )?;
+            let mut rows = stmt.query([&author_id])?;
+            while let Some(row) = rows.next()? {
+                let draft_id: u32 = row.get(0)?;
+                draft_ids.push(draft_id);
+            }
+        }
+
+        let mut drafts = Vec::new();
+        for draft_id in draft_ids {
+            drafts.push(drafts::get_draft_info(&trx, draft_id)?);
+        }
+
+        Ok(Json(drafts))
+    }, ErrorReporting::Json).await
+}
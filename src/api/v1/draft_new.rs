@@ -0,0 +1,119 @@
+
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+use monostate::MustBe;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::crypto::SignedMessage;
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::roles;
+use crate::sys_time;
+use crate::limits;
+use crate::drafts;
+
+use crate::api::v1::types::DraftPostSettings;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MsgDraftNew {
+    command: MustBe!("draft_new"),
+    channel_uuid: String,
+    post_uuid: Option<String>,
+    title: String,
+    text: String,
+    tags: Vec<String>,
+    publish_at: Option<u64>,
+
+    /// Slug, license, language, labels, content warning and visibility of the post.
+    #[serde(flatten)]
+    post_settings: DraftPostSettings,
+}
+
+pub async fn api_draft_new(
+    State(state): State<Arc<AppState>>,
+    Json(msg): Json<SignedMessage>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        // verify message
+        let public_key = msg.public_key()?.to_owned();
+        let msg = msg.verify()?;
+        let mut msg = serde_json::from_slice::<MsgDraftNew>(msg)?;
+
+        if msg.title.len() > limits::MAX_PAGE_TITLE_SIZE {
+            return Err(anyhow::anyhow!("Title is too long"));
+        }
+
+        if msg.text.len() > limits::MAX_PAGE_TEXT_SIZE {
+            return Err(anyhow::anyhow!("Text is too long"));
+        }
+
+        for tag in &msg.tags {
+            if tag.len() > limits::MAX_ITEM_NAME_SIZE {
+                return Err(anyhow::anyhow!("Tag is too long"));
+            }
+        }
+
+        drafts::validate_post_settings(&mut msg.post_settings)?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let author_id = trx.query_row(
+            "SELECT author.id FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+            [&public_key],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                Ok(author_id)
+            }
+        )?;
+
+        let channel_id = trx.query_row(
+            "SELECT channel.id FROM channel, channel_author WHERE channel.uuid = ? AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?",
+            (&msg.channel_uuid, &author_id),
+            |row| {
+                let channel_id: u32 = row.get(0)?;
+                Ok(channel_id)
+            }
+        )?;
+
+        let post_id = if let Some(post_uuid) = &msg.post_uuid {
+            let post_id = trx.query_row(
+                "SELECT id FROM post WHERE uuid = ? AND channel_id = ? AND is_deleted = 0",
+                (post_uuid, &channel_id),
+                |row| row.get::<_, u32>(0),
+            ).map_err(|_| anyhow::anyhow!("Post not found"))?;
+            roles::check_post_editable(&trx, channel_id, post_id, author_id)?;
+            Some(post_id)
+        } else {
+            None
+        };
+
+        let draft_uuid = uuid::Uuid::new_v4().to_string();
+        let updated_date = sys_time::get_sys_time_in_secs();
+        trx.execute(
+            "INSERT INTO draft (uuid, channel_id, post_id, author_id, updated_date, publish_date, title, draft_text) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            (&draft_uuid, &channel_id, &post_id, &author_id, &updated_date, &msg.publish_at, &msg.title, &msg.text),
+        )?;
+
+        let draft_id = trx.query_row(
+            "SELECT id FROM draft WHERE uuid = ?",
+            [&draft_uuid],
+            |row| row.get::<_, u32>(0),
+        )?;
+
+        drafts::set_draft_tags(&trx, draft_id, &msg.tags)?;
+        drafts::set_draft_post_settings(&trx, draft_id, &msg.post_settings)?;
+        let draft = drafts::get_draft_info(&trx, draft_id)?;
+
+        trx.commit()?;
+
+        Ok(Json(draft))
+    }, ErrorReporting::Json).await
+}
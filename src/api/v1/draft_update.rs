@@ -0,0 +1,102 @@
+
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+use monostate::MustBe;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::crypto::SignedMessage;
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::roles;
+use crate::sys_time;
+use crate::limits;
+use crate::drafts;
+
+use crate::api::v1::types::DraftPostSettings;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MsgDraftUpdate {
+    command: MustBe!("draft_update"),
+    uuid: String,
+    title: String,
+    text: String,
+    tags: Vec<String>,
+    publish_at: Option<u64>,
+
+    /// Slug, license, language, labels, content warning and visibility of the post.
+    #[serde(flatten)]
+    post_settings: DraftPostSettings,
+}
+
+pub async fn api_draft_update(
+    State(state): State<Arc<AppState>>,
+    Json(msg): Json<SignedMessage>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        // verify message
+        let public_key = msg.public_key()?.to_owned();
+        let msg = msg.verify()?;
+        let mut msg = serde_json::from_slice::<MsgDraftUpdate>(msg)?;
+
+        if msg.title.len() > limits::MAX_PAGE_TITLE_SIZE {
+            return Err(anyhow::anyhow!("Title is too long"));
+        }
+
+        if msg.text.len() > limits::MAX_PAGE_TEXT_SIZE {
+            return Err(anyhow::anyhow!("Text is too long"));
+        }
+
+        for tag in &msg.tags {
+            if tag.len() > limits::MAX_ITEM_NAME_SIZE {
+                return Err(anyhow::anyhow!("Tag is too long"));
+            }
+        }
+
+        drafts::validate_post_settings(&mut msg.post_settings)?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let author_id = trx.query_row(
+            "SELECT author.id FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+            [&public_key],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                Ok(author_id)
+            }
+        )?;
+
+        let draft_id = trx.query_row(
+            "SELECT draft.id FROM draft, channel, channel_author WHERE draft.uuid = ? AND draft.channel_id = channel.id AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?",
+            (&msg.uuid, &author_id),
+            |row| {
+                let draft_id: u32 = row.get(0)?;
+                Ok(draft_id)
+            }
+        )?;
+
+        roles::check_draft_editable(&trx, draft_id, author_id)?;
+
+        // the last editor is credited when the draft is published
+        let updated_date = sys_time::get_sys_time_in_secs();
+        trx.execute(
+            "UPDATE draft SET author_id = ?, updated_date = ?, publish_date = ?, title = ?, draft_text = ? WHERE id = ?",
+            (&author_id, &updated_date, &msg.publish_at, &msg.title, &msg.text, &draft_id),
+        )?;
+
+        drafts::set_draft_tags(&trx, draft_id, &msg.tags)?;
+        drafts::set_draft_post_settings(&trx, draft_id, &msg.post_settings)?;
+        let draft = drafts::get_draft_info(&trx, draft_id)?;
+
+        trx.commit()?;
+
+        Ok(Json(draft))
+    }, ErrorReporting::Json).await
+}
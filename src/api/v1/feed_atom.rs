@@ -0,0 +1,40 @@
+
+use std::sync::Arc;
+use std::collections::HashMap;
+
+use hyper::StatusCode;
+use axum::{
+    extract::{State, Query},
+    http::HeaderMap,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::feeds::{self, FeedSource, FeedContent};
+
+
+pub async fn api_feed_atom(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        let source = FeedSource::from_query(&params)?;
+        let content = FeedContent::from_query(params.get("content"))?;
+
+        let feed = if let Some(feed) = feeds::load_feed(&state, &source, content)? {
+            feed
+        } else {
+            return Ok((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({
+                    "status": "not found",
+                })),
+            ).into_response());
+        };
+
+        Ok(feeds::feed_response(&headers, "application/atom+xml; charset=utf-8", feed.updated_date(), feeds::render_atom(&feed)))
+    }, ErrorReporting::Json).await
+}
@@ -0,0 +1,60 @@
+
+use std::sync::Arc;
+
+use hyper::StatusCode;
+use axum::{
+    extract::{State, Path},
+    http::header,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::media;
+
+
+pub async fn api_media_file(
+    State(state): State<Arc<AppState>>,
+    Path(hash): Path<String>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        if !media::is_valid_sha256_hex(&hash) {
+            return Err(anyhow::anyhow!("Invalid hash"));
+        }
+
+        let mime_type = {
+            let mut db_connection = state.db_connection.lock().unwrap();
+            let trx = db_connection.transaction()?;
+
+            trx.query_row(
+                "SELECT mime_type FROM media WHERE sha256 = ?",
+                [&hash],
+                |row| row.get::<_, String>(0),
+            )
+        };
+
+        let (mime_type, data) = match mime_type.map(|mime_type| (mime_type, state.media_store.load(&hash))) {
+            Ok((mime_type, Ok(data))) => (mime_type, data),
+            _ => {
+                return Ok((
+                    StatusCode::NOT_FOUND,
+                    Json(serde_json::json!({
+                        "status": "not found",
+                    })),
+                ).into_response());
+            },
+        };
+
+        // files are addressed by their contents, so they never change
+        Ok((
+            [
+                (header::CONTENT_TYPE, mime_type),
+                (header::CACHE_CONTROL, "public, max-age=31536000, immutable".to_string()),
+                (header::ETAG, format!("\"{}\"", hash)),
+                (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+            ],
+            data,
+        ).into_response())
+    }, ErrorReporting::Json).await
+}
@@ -0,0 +1,78 @@
+
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+use monostate::MustBe;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::crypto::SignedMessage;
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::sys_time;
+use crate::limits;
+use crate::media;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MsgMediaUpload {
+    command: MustBe!("media_upload"),
+
+    #[serde(with="crate::base64")]
+    data: Vec<u8>,
+}
+
+pub async fn api_media_upload(
+    State(state): State<Arc<AppState>>,
+    Json(msg): Json<SignedMessage>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        // verify message
+        let public_key = msg.public_key()?.to_owned();
+        let msg = msg.verify()?;
+        let msg = serde_json::from_slice::<MsgMediaUpload>(msg)?;
+
+        if msg.data.len() > limits::MAX_MEDIA_SIZE {
+            return Err(anyhow::anyhow!("File is too large"));
+        }
+
+        let mime_type = media::detect_mime_type(&msg.data).ok_or_else(|| anyhow::anyhow!("Unsupported file type"))?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let author_id = trx.query_row(
+            "SELECT author.id FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+            [&public_key],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                Ok(author_id)
+            }
+        )?;
+
+        let hash = state.media_store.store(&msg.data)?;
+        let uploaded_date = sys_time::get_sys_time_in_secs();
+
+        // the first uploader is kept for identical files, but the upload date is refreshed
+        // so that garbage collection spares a file uploaded again to be referenced
+        trx.execute(
+            "INSERT INTO media (sha256, mime_type, size, author_id, uploaded_date) VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT (sha256) DO UPDATE SET uploaded_date = excluded.uploaded_date",
+            (&hash, mime_type, msg.data.len(), &author_id, &uploaded_date),
+        )?;
+
+        trx.commit()?;
+
+        Ok(Json(serde_json::json!({
+            "status": "ok",
+            "sha256": hash,
+            "mime_type": mime_type,
+            "size": msg.data.len(),
+            "url": format!("{}{}", media::MEDIA_URL_PREFIX, hash),
+        })))
+    }, ErrorReporting::Json).await
+}
@@ -11,6 +11,9 @@ use hyper::StatusCode;
 
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::markdown;
+use crate::meta_pages;
+use crate::api::v1::types::{is_html_format_requested, normalize_language_code};
 
 
 pub async fn api_meta_info(
@@ -19,14 +22,33 @@ pub async fn api_meta_info(
 ) -> impl IntoResponse {
     result_into_response(async move {
         let page_name = params.get("page_name").ok_or_else(|| anyhow::anyhow!("Missing page_name parameter"))?;
+        let render_html = is_html_format_requested(params.get("format"))?;
+        let lang = params.get("lang").map(|lang| normalize_language_code(lang)).transpose()?;
 
         let mut db_connection = state.db_connection.lock().unwrap();
 
         let transaction = db_connection.transaction()?;
 
+        let (meta_page_id, page_lang) = if let Some(values) = meta_pages::find_meta_page(&transaction, page_name, lang.as_deref())? {
+            values
+        } else {
+            return Ok((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({
+                    "status": "error",
+                    "error": "not found",
+                })),
+            ).into_response());
+        };
+        // the default variant is listed as null
+        let langs: Vec<Option<String>> = meta_pages::get_meta_page_languages(&transaction, page_name)?
+            .into_iter()
+            .map(|lang| if lang.is_empty() { None } else { Some(lang) })
+            .collect();
+
         let (title, updated_date, page_text) = if let Ok(values) = transaction.query_row(
-            "SELECT title, updated_date, page_text FROM meta_page WHERE page_name = ?", 
-            [page_name],
+            "SELECT title, updated_date, page_text FROM meta_page WHERE id = ?", 
+            [meta_page_id],
             |row| {
                 let title: String = row.get(0)?;
                 let updated_date: u64 = row.get(1)?;
@@ -45,12 +67,20 @@ pub async fn api_meta_info(
                 })),
             ).into_response());
         };
-        
-        Ok(Json(serde_json::json!({
+
+        let mut page = serde_json::json!({
             "page_name": page_name,
             "updated_date": updated_date,
             "title": title,
             "text": &page_text,
-        })).into_response())
+            "lang": if page_lang.is_empty() { None } else { Some(&page_lang) },
+            "langs": langs,
+        });
+
+        if render_html {
+            page["rendered_html"] = serde_json::json!(markdown::render_markdown(&page_text));
+        }
+
+        Ok(Json(page).into_response())
     }, ErrorReporting::Json).await
 }
@@ -22,21 +22,33 @@ pub async fn api_meta_list(
         let transaction = db_connection.transaction()?;
 
         let mut stmt = transaction.prepare(
-            "SELECT page_name, title, updated_date FROM meta_page ORDER BY updated_date DESC LIMIT 1000",
+            "SELECT page_name, title, updated_date, language_code FROM meta_page WHERE is_deleted = 0 ORDER BY updated_date DESC LIMIT 1000",
         )?;
 
         let mut rows = stmt.query([])?;
 
+        // one entry per page, titled after the default variant if there is one
         let mut pages = Vec::<serde_json::Value>::new();
         while let Some(row) = rows.next()? {
             let page_name: String = row.get(0)?;
             let title: String = row.get(1)?;
             let updated_date: u64 = row.get(2)?;
+            let lang: String = row.get(3)?;
+            let lang = if lang.is_empty() { None } else { Some(lang) };
+
+            if let Some(page) = pages.iter_mut().find(|page| page["page_name"] == page_name) {
+                if lang.is_none() {
+                    page["title"] = serde_json::json!(title);
+                }
+                page["langs"].as_array_mut().unwrap().push(serde_json::json!(lang));
+                continue;
+            }
 
             pages.push(serde_json::json!({
                 "page_name": page_name,
                 "updated_date": updated_date,
                 "title": title,
+                "langs": [lang],
             }));
         }
         
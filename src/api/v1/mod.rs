@@ -11,17 +11,40 @@ mod account_delete;
 
 mod admin_meta_update;
 mod admin_meta_delete;
+mod admin_meta_revisions;
+mod admin_meta_revision_info;
+mod admin_meta_rollback;
 mod admin_author_delete;
 mod admin_channel_delete;
 mod admin_post_delete;
+mod admin_featured_update;
+mod admin_post_labels;
 
 mod self_update;
 mod channel_new;
 mod channel_update;
 mod channel_delete;
+mod channel_add_author;
+mod channel_remove_author;
+mod channel_set_author_role;
+mod channel_transfer_offer;
+mod channel_transfer_accept;
 mod post_new;
 mod post_update;
 mod post_delete;
+mod post_link_translation;
+mod post_unlink_translation;
+mod post_read;
+mod revision_delete;
+mod draft_new;
+mod draft_update;
+mod draft_delete;
+mod draft_publish;
+mod draft_info;
+mod draft_list;
+mod media_upload;
+mod comment_new;
+mod comment_delete;
 
 mod meta_info;
 mod meta_list;
@@ -33,10 +56,24 @@ mod channel_info;
 mod channel_list;
 mod channel_authors;
 mod channel_posts;
+mod channel_pinned;
+mod channel_set_pins;
+mod channel_history;
 mod post_info;
 mod post_list;
+mod post_featured;
+mod post_revisions;
+mod post_comments;
+mod revision_info;
+mod revision_diff;
+mod media_file;
 mod tag_list;
 mod tag_posts;
+mod feed_atom;
+mod feed_rss;
+mod feed_json;
+mod sitemap;
+mod sitemap_index;
 
 
 // API handlers
@@ -49,17 +86,40 @@ pub use account_delete::api_account_delete;
 
 pub use admin_meta_update::api_admin_meta_update;
 pub use admin_meta_delete::api_admin_meta_delete;
+pub use admin_meta_revisions::api_admin_meta_revisions;
+pub use admin_meta_revision_info::api_admin_meta_revision_info;
+pub use admin_meta_rollback::api_admin_meta_rollback;
 pub use admin_author_delete::api_admin_author_delete;
 pub use admin_channel_delete::api_admin_channel_delete;
 pub use admin_post_delete::api_admin_post_delete;
+pub use admin_featured_update::api_admin_featured_update;
+pub use admin_post_labels::api_admin_post_labels;
 
 pub use self_update::api_self_update;
 pub use channel_new::api_channel_new;
 pub use channel_update::api_channel_update;
 pub use channel_delete::api_channel_delete;
+pub use channel_add_author::api_channel_add_author;
+pub use channel_remove_author::api_channel_remove_author;
+pub use channel_set_author_role::api_channel_set_author_role;
+pub use channel_transfer_offer::api_channel_transfer_offer;
+pub use channel_transfer_accept::api_channel_transfer_accept;
 pub use post_new::api_post_new;
 pub use post_update::api_post_update;
 pub use post_delete::api_post_delete;
+pub use post_link_translation::api_post_link_translation;
+pub use post_unlink_translation::api_post_unlink_translation;
+pub use post_read::api_post_read;
+pub use revision_delete::api_revision_delete;
+pub use draft_new::api_draft_new;
+pub use draft_update::api_draft_update;
+pub use draft_delete::api_draft_delete;
+pub use draft_publish::api_draft_publish;
+pub use draft_info::api_draft_info;
+pub use draft_list::api_draft_list;
+pub use media_upload::api_media_upload;
+pub use comment_new::api_comment_new;
+pub use comment_delete::api_comment_delete;
 
 pub use meta_info::api_meta_info;
 pub use meta_list::api_meta_list;
@@ -71,7 +131,21 @@ pub use channel_info::api_channel_info;
 pub use channel_list::api_channel_list;
 pub use channel_authors::api_channel_authors;
 pub use channel_posts::api_channel_posts;
+pub use channel_pinned::api_channel_pinned;
+pub use channel_set_pins::api_channel_set_pins;
+pub use channel_history::api_channel_history;
 pub use post_info::api_post_info;
 pub use post_list::api_post_list;
+pub use post_featured::api_post_featured;
+pub use post_revisions::api_post_revisions;
+pub use post_comments::api_post_comments;
+pub use revision_info::api_revision_info;
+pub use revision_diff::api_revision_diff;
+pub use media_file::api_media_file;
 pub use tag_list::api_tag_list;
 pub use tag_posts::api_tag_posts;
+pub use feed_atom::api_feed_atom;
+pub use feed_rss::api_feed_rss;
+pub use feed_json::api_feed_json;
+pub use sitemap::api_sitemap;
+pub use sitemap_index::api_sitemap_index;
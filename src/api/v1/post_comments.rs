@@ -0,0 +1,95 @@
+
+use std::sync::Arc;
+use std::collections::HashMap;
+
+use hyper::StatusCode;
+use axum::{
+    extract::{State, Query},
+    response::IntoResponse,
+    Json,
+};
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::icons;
+use crate::pagination::{Cursor, PageParams};
+
+use crate::api::v1::types::{
+    AuthorSummary,
+    CommentInfo,
+};
+
+
+pub async fn api_post_comments(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        let post_uuid = params.get("uuid").ok_or_else(|| anyhow::anyhow!("Missing uuid parameter"))?;
+
+        // comments are paged in ascending order, oldest first
+        let page = PageParams::<u32>::from_query(&params)?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        if trx.query_row(
+            "SELECT post.id FROM post, channel WHERE post.uuid = ? AND post.is_deleted = 0 AND post.visibility != 'private' AND post.channel_id = channel.id AND channel.is_deleted = 0",
+            [post_uuid],
+            |row| row.get::<_, u32>(0),
+        ).is_err() {
+            return Ok((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({
+                    "status": "not found",
+                })),
+            ).into_response());
+        }
+
+        // comments by deleted authors are shown as deleted, to keep threads intact
+        let mut stmt = trx.prepare(
+            &format!(
+                "
+                    SELECT comment.uuid, parent.uuid, comment.created_date, comment.is_deleted OR author.is_deleted, comment.comment_text, author.uuid, author.name,
+                    {author_icon},
+                    comment.id
+                    FROM post, comment
+                    INNER JOIN author ON comment.author_id = author.id
+                    LEFT JOIN comment AS parent ON comment.parent_id = parent.id
+                    WHERE post.uuid = ?1 AND comment.post_id = post.id
+                    AND (?2 IS NULL OR comment.created_date > ?2 OR (comment.created_date = ?2 AND comment.id > ?3))
+                    ORDER BY comment.created_date ASC, comment.id ASC
+                    LIMIT ?4
+                ",
+                author_icon = icons::author_summary_icon_query("author.id"),
+            )
+        )?;
+
+        let mut rows = stmt.query((post_uuid, page.cursor_key(), page.cursor_id(), page.query_limit()))?;
+        let mut comments = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let comment_uuid: String = row.get(0)?;
+            let parent_uuid: Option<String> = row.get(1)?;
+            let created_date: u64 = row.get(2)?;
+            let is_deleted: bool = row.get(3)?;
+            let comment_id: u32 = row.get(8)?;
+            let cursor = Cursor::new(created_date, comment_id);
+
+            if is_deleted {
+                comments.push((cursor, serde_json::json!(CommentInfo::new_deleted(&comment_uuid, post_uuid, parent_uuid.as_deref(), created_date))));
+                continue;
+            }
+
+            let comment_text: String = row.get(4)?;
+            let author_uuid: String = row.get(5)?;
+            let author_name: String = row.get(6)?;
+            let author_icon_sha256: Option<String> = row.get(7)?;
+
+            let author = AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref());
+            comments.push((cursor, serde_json::json!(CommentInfo::new(&comment_uuid, post_uuid, parent_uuid.as_deref(), &author, created_date, &comment_text))));
+        }
+
+        Ok(Json(page.into_response(comments)).into_response())
+    }, ErrorReporting::Json).await
+}
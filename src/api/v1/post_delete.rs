@@ -13,6 +13,7 @@ use axum::{
 use crate::crypto::SignedMessage;
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::roles;
 
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,7 +30,7 @@ pub async fn api_post_delete(
         // verify message
         let public_key = msg.public_key()?.to_owned();
         let msg = msg.verify()?;
-        let msg = serde_json::from_slice::<MsgPostDelete>(&msg)?;
+        let msg = serde_json::from_slice::<MsgPostDelete>(msg)?;
 
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
@@ -43,15 +44,18 @@ pub async fn api_post_delete(
             }
         )?;
 
-        let post_id = trx.query_row(
-            "SELECT post.id FROM post, channel, channel_author WHERE post.uuid = ? AND post.is_deleted = 0 AND post.channel_id = channel.id AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?",
+        let (post_id, channel_id) = trx.query_row(
+            "SELECT post.id, channel.id FROM post, channel, channel_author WHERE post.uuid = ? AND post.is_deleted = 0 AND post.channel_id = channel.id AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?",
             (&msg.uuid, &author_id),
             |row| {
                 let post_id: u32 = row.get(0)?;
-                Ok(post_id)
+                let channel_id: u32 = row.get(1)?;
+                Ok((post_id, channel_id))
             }
         )?;
 
+        roles::check_post_editable(&trx, channel_id, post_id, author_id)?;
+
         trx.execute(
             "UPDATE post SET is_deleted = 1 WHERE id = ?",
             (&post_id,),
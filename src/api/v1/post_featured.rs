@@ -0,0 +1,100 @@
+
+use std::sync::Arc;
+use std::collections::HashMap;
+
+use axum::{
+    extract::{State, Query},
+    response::IntoResponse,
+    Json,
+};
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::labels;
+use crate::listings;
+use crate::icons;
+
+use crate::api::v1::types::{
+    AuthorSummary,
+    ChannelSummary,
+};
+
+
+pub async fn api_post_featured(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        // posts with any of the labels, or with any label or content warning if `all`
+        let (exclude_all_labels, exclude_labels) = labels::parse_exclude_labels(params.get("exclude_labels"))?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let mut stmt = trx.prepare(
+            &format!(
+                "
+                    SELECT post.uuid, revision.uuid, MAX(revision.created_date), revision.title, author.uuid, author.name, channel.uuid, channel.handle, channel.name, channel.language_code, post_slug.slug,
+                    {author_icon},
+                    {channel_icon},
+                    COALESCE(post.language_code, channel.language_code),
+                    {post_labels}, post.content_warning, post.published_date, post.updated_date
+                    FROM {post_tables}, featured_post
+                    WHERE {post_conditions}
+                    AND featured_post.post_id = post.id
+                    AND {exclude_labels}
+                    GROUP BY post.id
+                    ORDER BY featured_post.position ASC
+                ",
+                author_icon = icons::author_summary_icon_query("author.id"), channel_icon = icons::channel_summary_icon_query("channel.id"),
+                post_tables = listings::POST_LISTING_TABLES,
+                post_conditions = listings::POST_LISTING_CONDITIONS,
+                post_labels = labels::POST_LABELS_COLUMN,
+                exclude_labels = labels::exclude_labels_condition("?1", "?2"),
+            )
+        )?;
+
+        let mut rows = stmt.query((exclude_all_labels, &exclude_labels))?;
+        let mut posts = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let post_uuid: String = row.get(0)?;
+            let revision_uuid: String = row.get(1)?;
+            let revision_date: u64 = row.get(2)?;
+            let title: String = row.get(3)?;
+            let author_uuid: String = row.get(4)?;
+            let author_name: String = row.get(5)?;
+            let channel_uuid: String = row.get(6)?;
+            let channel_handle: String = row.get(7)?;
+            let channel_name: String = row.get(8)?;
+            let channel_lang: String = row.get(9)?;
+            let slug: Option<String> = row.get(10)?;
+            let author_icon_sha256: Option<String> = row.get(11)?;
+            let channel_icon_sha256: Option<String> = row.get(12)?;
+            let lang: String = row.get(13)?;
+            let post_labels: Option<String> = row.get(14)?;
+            let content_warning: Option<String> = row.get(15)?;
+            let published_date: u64 = row.get(16)?;
+            let updated_date: u64 = row.get(17)?;
+
+            let author = AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref());
+            let channel = ChannelSummary::new(&channel_uuid, &channel_handle, &channel_name, &channel_lang, channel_icon_sha256.as_deref());
+            posts.push(serde_json::json!({
+                "post_uuid": post_uuid,
+                "slug": slug,
+                "revision_uuid": revision_uuid,
+                "revision_date": revision_date,
+                "published_date": published_date,
+                "updated_date": updated_date,
+                "title": title,
+                "author": author,
+                "channel": channel,
+                "lang": lang,
+                "labels": labels::split_labels(post_labels),
+                "content_warning": content_warning,
+            }));
+        }
+
+        Ok(Json(serde_json::json!(posts)))
+    }, ErrorReporting::Json).await
+}
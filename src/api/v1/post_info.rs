@@ -5,91 +5,83 @@ use std::collections::HashMap;
 use hyper::StatusCode;
 use axum::{
     extract::{State, Query},
-    response::IntoResponse,
+    response::{IntoResponse, Redirect},
     Json,
 };
 
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::translations;
+use crate::labels;
+use crate::icons;
 
 use crate::api::v1::types::{
+    is_html_format_requested,
     ChannelSummary,
     AuthorSummary,
 };
 
 
+enum QueryType {
+    ByUuid(String),
+    BySlug(String, String),
+}
+
 pub async fn api_post_info(
     State(state): State<Arc<AppState>>,
     Query(params): Query<HashMap<String, String>>,
 ) -> impl IntoResponse {
     result_into_response(async move {
-        let post_uuid = if let Some(post_uuid) = params.get("uuid") {
-            post_uuid
-        } else {
-            return Err(anyhow::anyhow!("Missing uuid parameter"));
+        let maybe_post_uuid = params.get("uuid");
+        let maybe_channel_handle = params.get("channel_handle");
+        let maybe_slug = params.get("slug");
+        let render_html = is_html_format_requested(params.get("format"))?;
+
+        let query_type = match (maybe_post_uuid, maybe_channel_handle, maybe_slug) {
+            (Some(post_uuid), None, None) => QueryType::ByUuid(post_uuid.to_owned()),
+            (None, Some(channel_handle), Some(slug)) => QueryType::BySlug(channel_handle.to_owned(), slug.to_owned()),
+            _ => return Err(anyhow::anyhow!("Either uuid, or channel_handle and slug parameters are required")),
         };
 
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
-        let mut stmt = trx.prepare(
-            "SELECT DISTINCT post_tag.name FROM post_tag INNER JOIN post ON post_tag.post_id = post.id WHERE post.is_deleted = 0 AND post.uuid = ?"
-        )?;
-        let mut tag_rows = stmt.query([post_uuid])?;
-        let mut tags: Vec<String> = Vec::new();
-        while let Some(tag_row) = tag_rows.next()? {
-            let tag: String = tag_row.get(0)?;
-            tags.push(tag);
-        }
+        let post_uuid = match query_type {
+            QueryType::ByUuid(post_uuid) => post_uuid,
+            QueryType::BySlug(channel_handle, slug) => {
+                let (post_uuid, is_current) = if let Ok(values) = trx.query_row(
+                    "SELECT post.uuid, post_slug.is_current FROM channel, post, post_slug WHERE channel.handle = ? AND post_slug.slug = ? AND channel.is_deleted = 0 AND post.is_deleted = 0 AND post.visibility != 'private' AND post_slug.channel_id = channel.id AND post_slug.post_id = post.id",
+                    [channel_handle, slug],
+                    |row| {
+                        let post_uuid: String = row.get(0)?;
+                        let is_current: bool = row.get(1)?;
+                        Ok((post_uuid, is_current))
+                    }
+                ) {
+                    values
+                } else {
+                    return Ok((
+                        StatusCode::NOT_FOUND,
+                        Json(serde_json::json!({
+                            "status": "not found",
+                        })),
+                    ).into_response());
+                };
 
-        drop(tag_rows);
-        drop(stmt);
-
-        let (
-            channel_uuid,
-            channel_handle,
-            channel_name,
-            channel_lang,
-            revision_uuid,
-            revision_date,
-            title,
-            author_uuid,
-            author_name,
-            revision_text,
-        ) = if let Ok(values) = trx.query_row(
-            "
-                SELECT channel.uuid, channel.handle, channel.name, channel.language_code, revision.uuid, revision.created_date, revision.title, author.uuid, author.name, revision.revision_text
-                FROM channel, post, revision, author
-                WHERE channel.is_deleted = 0 AND post.is_deleted = 0 AND revision.is_deleted = 0 AND author.is_deleted = 0 AND post.uuid = ? AND post.channel_id = channel.id AND post.id = revision.post_id AND revision.author_id = author.id
-                ORDER BY revision.created_date DESC LIMIT 1
-            ",
-            [post_uuid],
-            |row| {
-                let channel_uuid: String = row.get(0)?;
-                let channel_handle: String = row.get(1)?;
-                let channel_name: String = row.get(2)?;
-                let channel_lang: String = row.get(3)?;
-                let revision_uuid: String = row.get(4)?;
-                let revision_date: u64 = row.get(5)?;
-                let title: String = row.get(6)?;
-                let author_uuid: String = row.get(7)?;
-                let author_name: String = row.get(8)?;
-                let revision_text: String = row.get(9)?;
-                Ok((
-                    channel_uuid,
-                    channel_handle,
-                    channel_name,
-                    channel_lang,
-                    revision_uuid,
-                    revision_date,
-                    title,
-                    author_uuid,
-                    author_name,
-                    revision_text,
-                ))
-            }
-        ) {
-            values
+                // old slugs redirect to the post
+                if !is_current {
+                    let mut location = format!("/api/v1/post/info?uuid={}", post_uuid);
+                    if render_html {
+                        location.push_str("&format=html");
+                    }
+                    return Ok(Redirect::permanent(&location).into_response());
+                }
+
+                post_uuid
+            },
+        };
+        let mut post = if let Some(post) = load_post_info(&trx, &post_uuid, false)? {
+            post
         } else {
             return Ok((
                 StatusCode::NOT_FOUND,
@@ -99,18 +91,149 @@ pub async fn api_post_info(
             ).into_response());
         };
 
-        let channel = ChannelSummary::new(&channel_uuid, &channel_handle, &channel_name, &channel_lang);
-        let author = AuthorSummary::new(&author_uuid, &author_name);
-
-        Ok(Json(serde_json::json!({
-            "post_uuid": post_uuid,
-            "channel": channel,
-            "tags": tags,
-            "revision_uuid": revision_uuid,
-            "revision_date": revision_date,
-            "title": title,
-            "revision_text": revision_text,
-            "author": author,
-        })).into_response())
+        if render_html {
+            let rendered_html = render_post_html(&state, &post);
+            post["rendered_html"] = serde_json::json!(rendered_html);
+        }
+
+        Ok(Json(post).into_response())
     }, ErrorReporting::Json).await
 }
+
+/// Loads a post as returned by `/api/v1/post/info`, or `None` if it is not found.
+/// Private posts are only loaded with `include_private`.
+pub(super) fn load_post_info(conn: &rusqlite::Connection, post_uuid: &str, include_private: bool) -> anyhow::Result<Option<serde_json::Value>> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT post_tag.name FROM post_tag INNER JOIN post ON post_tag.post_id = post.id WHERE post.is_deleted = 0 AND post.uuid = ?"
+    )?;
+    let mut tag_rows = stmt.query([post_uuid])?;
+    let mut tags: Vec<String> = Vec::new();
+    while let Some(tag_row) = tag_rows.next()? {
+        let tag: String = tag_row.get(0)?;
+        tags.push(tag);
+    }
+
+    drop(tag_rows);
+    drop(stmt);
+
+    let (
+        channel_uuid,
+        channel_handle,
+        channel_name,
+        channel_lang,
+        revision_uuid,
+        revision_date,
+        title,
+        author_uuid,
+        author_name,
+        revision_text,
+        slug,
+        author_icon_sha256,
+        channel_icon_sha256,
+        license,
+        lang,
+        post_id,
+        content_warning,
+        visibility,
+        published_date,
+        updated_date,
+    ) = if let Ok(values) = conn.query_row(
+        &format!(
+            "
+                SELECT channel.uuid, channel.handle, channel.name, channel.language_code, revision.uuid, revision.created_date, revision.title, author.uuid, author.name, revision.revision_text, post_slug.slug,
+                {author_icon},
+                {channel_icon},
+                COALESCE(post.license, channel.license), COALESCE(post.language_code, channel.language_code), post.id, post.content_warning, post.visibility, post.published_date, post.updated_date
+                FROM channel, post, revision, author
+                LEFT JOIN post_slug ON post_slug.post_id = post.id AND post_slug.is_current = 1
+                WHERE channel.is_deleted = 0 AND post.is_deleted = 0 AND (?2 OR post.visibility != 'private') AND revision.is_deleted = 0 AND author.is_deleted = 0 AND post.uuid = ?1 AND post.channel_id = channel.id AND post.id = revision.post_id AND revision.author_id = author.id
+                ORDER BY revision.created_date DESC, revision.id DESC LIMIT 1
+            ",
+            author_icon = icons::author_summary_icon_query("author.id"), channel_icon = icons::channel_summary_icon_query("channel.id"),
+        ),
+        (post_uuid, include_private),
+        |row| {
+            let channel_uuid: String = row.get(0)?;
+            let channel_handle: String = row.get(1)?;
+            let channel_name: String = row.get(2)?;
+            let channel_lang: String = row.get(3)?;
+            let revision_uuid: String = row.get(4)?;
+            let revision_date: u64 = row.get(5)?;
+            let title: String = row.get(6)?;
+            let author_uuid: String = row.get(7)?;
+            let author_name: String = row.get(8)?;
+            let revision_text: String = row.get(9)?;
+            let slug: Option<String> = row.get(10)?;
+            let author_icon_sha256: Option<String> = row.get(11)?;
+            let channel_icon_sha256: Option<String> = row.get(12)?;
+            let license: String = row.get(13)?;
+            let lang: String = row.get(14)?;
+            let post_id: u32 = row.get(15)?;
+            let content_warning: Option<String> = row.get(16)?;
+            let visibility: String = row.get(17)?;
+            let published_date: u64 = row.get(18)?;
+            let updated_date: u64 = row.get(19)?;
+            Ok((
+                channel_uuid,
+                channel_handle,
+                channel_name,
+                channel_lang,
+                revision_uuid,
+                revision_date,
+                title,
+                author_uuid,
+                author_name,
+                revision_text,
+                slug,
+                author_icon_sha256,
+                channel_icon_sha256,
+                license,
+                lang,
+                post_id,
+                content_warning,
+                visibility,
+                published_date,
+                updated_date,
+            ))
+        }
+    ) {
+        values
+    } else {
+        return Ok(None);
+    };
+
+    let translations = translations::get_post_translations(conn, post_id)?;
+    let labels = labels::get_post_labels(conn, post_id)?;
+
+    let channel = ChannelSummary::new(&channel_uuid, &channel_handle, &channel_name, &channel_lang, channel_icon_sha256.as_deref());
+    let author = AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref());
+
+    let post = serde_json::json!({
+        "post_uuid": post_uuid,
+        "slug": slug,
+        "channel": channel,
+        "tags": tags,
+        "revision_uuid": revision_uuid,
+        "revision_date": revision_date,
+        "published_date": published_date,
+        "updated_date": updated_date,
+        "title": title,
+        "revision_text": revision_text,
+        "author": author,
+        "license": license,
+        "lang": lang,
+        "translations": translations,
+        "labels": labels,
+        "content_warning": content_warning,
+        "visibility": visibility,
+    });
+
+    Ok(Some(post))
+}
+
+/// Renders the revision text of a post loaded with `load_post_info`.
+pub(super) fn render_post_html(state: &AppState, post: &serde_json::Value) -> String {
+    let revision_uuid = post["revision_uuid"].as_str().unwrap_or_default();
+    let revision_text = post["revision_text"].as_str().unwrap_or_default();
+    state.markdown_cache.lock().unwrap().render(revision_uuid, revision_text)
+}
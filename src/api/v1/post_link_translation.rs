@@ -0,0 +1,76 @@
+
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+use monostate::MustBe;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::crypto::SignedMessage;
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::roles;
+use crate::translations;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MsgPostLinkTranslation {
+    command: MustBe!("post_link_translation"),
+    uuid: String,
+    translation_uuid: String,
+}
+
+pub async fn api_post_link_translation(
+    State(state): State<Arc<AppState>>,
+    Json(msg): Json<SignedMessage>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        // verify message
+        let public_key = msg.public_key()?.to_owned();
+        let msg = msg.verify()?;
+        let msg = serde_json::from_slice::<MsgPostLinkTranslation>(msg)?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let author_id = trx.query_row(
+            "SELECT author.id FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+            [&public_key],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                Ok(author_id)
+            }
+        )?;
+
+        // both posts must be editable by the author, possibly in different channels
+        let mut post_ids = Vec::new();
+        for post_uuid in [&msg.uuid, &msg.translation_uuid] {
+            let (post_id, channel_id) = trx.query_row(
+                "SELECT post.id, channel.id FROM post, channel, channel_author WHERE post.uuid = ? AND post.is_deleted = 0 AND post.channel_id = channel.id AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?",
+                (post_uuid, &author_id),
+                |row| {
+                    let post_id: u32 = row.get(0)?;
+                    let channel_id: u32 = row.get(1)?;
+                    Ok((post_id, channel_id))
+                }
+            ).map_err(|_| anyhow::anyhow!("Post not found"))?;
+
+            roles::check_post_editable(&trx, channel_id, post_id, author_id)?;
+            post_ids.push(post_id);
+        }
+
+        translations::link_translation(&trx, post_ids[0], post_ids[1])?;
+        let translations = translations::get_post_translations(&trx, post_ids[0])?;
+
+        trx.commit()?;
+
+        Ok(Json(serde_json::json!({
+            "status": "ok",
+            "translations": translations,
+        })))
+    }, ErrorReporting::Json).await
+}
@@ -10,34 +10,136 @@ use axum::{
 
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::labels;
+use crate::listings;
+use crate::pagination::{Cursor, PageParams};
+use crate::icons;
 
 use crate::api::v1::types::{
+    normalize_language_code,
+    validate_v4_uuid,
     AuthorSummary,
     ChannelSummary,
 };
 
 
+/// Parses a comma-separated list parameter, without duplicates.
+/// Returns `None` if the parameter is missing or empty.
+fn parse_list_param(value: Option<&String>) -> Option<Vec<String>> {
+    let value = match value.map(|value| value.as_str()) {
+        None | Some("") => return None,
+        Some(value) => value,
+    };
+    let mut items: Vec<String> = value.split(',').map(|item| item.to_string()).collect();
+    items.sort();
+    items.dedup();
+    Some(items)
+}
+
+/// Parses a comma-separated list of uuids into a JSON array to be used with `json_each()`.
+fn parse_uuid_list_param(value: Option<&String>) -> anyhow::Result<Option<String>> {
+    let uuids = parse_list_param(value);
+    if let Some(uuids) = &uuids {
+        for uuid in uuids {
+            validate_v4_uuid(uuid)?;
+        }
+    }
+    Ok(uuids.map(|uuids| serde_json::json!(uuids).to_string()))
+}
+
+fn parse_date_param(value: Option<&String>) -> anyhow::Result<Option<u64>> {
+    value.map(|value| value.parse().map_err(|_| anyhow::anyhow!("Invalid date"))).transpose()
+}
+
 pub async fn api_post_list(
     State(state): State<Arc<AppState>>,
-    Query(_params): Query<HashMap<String, String>>,
+    Query(params): Query<HashMap<String, String>>,
 ) -> impl IntoResponse {
     result_into_response(async move {
+        // posts in the given language or one of its sublanguages, e.g. `en` matches `en-US`
+        let lang = params.get("lang").map(|lang| normalize_language_code(lang)).transpose()?;
+
+        // posts with any of the labels, or with any label or content warning if `all`
+        let (exclude_all_labels, exclude_labels) = labels::parse_exclude_labels(params.get("exclude_labels"))?;
+
+        // posts in any of the channels, and with a revision by any of the authors
+        let channels = parse_uuid_list_param(params.get("channels"))?;
+        let authors = parse_uuid_list_param(params.get("authors"))?;
+
+        // posts with any (or all, with `tag_mode=all`) of the tags
+        let tags = parse_list_param(params.get("tags")).map(|tags| serde_json::json!(tags).to_string());
+        let all_tags = match params.get("tag_mode").map(|mode| mode.as_str()) {
+            None | Some("any") => false,
+            Some("all") => true,
+            Some(_) => return Err(anyhow::anyhow!("Invalid tag_mode")),
+        };
+
+        // dates in seconds since UNIX epoch; `after` is inclusive and `before` is exclusive
+        let published_after = parse_date_param(params.get("published_after"))?;
+        let published_before = parse_date_param(params.get("published_before"))?;
+        let updated_after = parse_date_param(params.get("updated_after"))?;
+        let updated_before = parse_date_param(params.get("updated_before"))?;
+
+        // newest first, either by the publication date or by the date of the latest update that is not a minor edit
+        let sort = params.get("sort").map(|sort| sort.as_str()).unwrap_or("updated");
+        let sort_by_published = match sort {
+            "updated" => false,
+            "published" => true,
+            _ => return Err(anyhow::anyhow!("Invalid sort")),
+        };
+
+        // the cursor records the sort order it was made for, since its date is meaningless in the other one
+        let page = PageParams::<(u32, String)>::from_query(&params)?;
+        let cursor_post_id = page.cursor_id().map(|(post_id, _)| *post_id);
+        if page.cursor_id().is_some_and(|(_, cursor_sort)| cursor_sort != sort) {
+            return Err(anyhow::anyhow!("Invalid cursor"));
+        }
+
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
         let mut stmt = trx.prepare(
-            "
-                SELECT post.uuid, revision.uuid, revision.created_date, revision.title, author.uuid, author.name, channel.uuid, channel.handle, channel.name, channel.language_code
-                FROM channel, post, revision, author
-                WHERE channel.is_deleted = 0 AND post.is_deleted = 0 AND revision.is_deleted = 0 AND author.is_deleted = 0 
-                AND channel.id = post.channel_id AND post.id = revision.post_id AND revision.author_id = author.id
-                GROUP BY post.id
-                ORDER BY revision.created_date DESC
-                LIMIT 1000
-            "
+            &format!(
+                "
+                    SELECT post.uuid, revision.uuid, MAX(revision.created_date), revision.title, author.uuid, author.name, channel.uuid, channel.handle, channel.name, channel.language_code, post_slug.slug,
+                    {author_icon},
+                    {channel_icon},
+                    COALESCE(post.language_code, channel.language_code),
+                    EXISTS (SELECT 1 FROM featured_post WHERE featured_post.post_id = post.id),
+                    {post_labels}, post.content_warning, post.id, post.published_date, post.updated_date
+                    FROM {post_tables}
+                    WHERE {post_conditions}
+                    AND (?1 IS NULL OR COALESCE(post.language_code, channel.language_code) = ?1 OR COALESCE(post.language_code, channel.language_code) LIKE ?1 || '-%')
+                    AND {exclude_labels}
+                    AND (?7 IS NULL OR channel.uuid IN (SELECT value FROM json_each(?7)))
+                    AND (?8 IS NULL OR EXISTS (
+                        SELECT 1 FROM revision AS author_revision, author AS revision_author
+                        WHERE author_revision.post_id = post.id AND author_revision.is_deleted = 0 AND author_revision.author_id = revision_author.id
+                        AND revision_author.uuid IN (SELECT value FROM json_each(?8))
+                    ))
+                    AND (?9 IS NULL OR (
+                        SELECT COUNT(DISTINCT post_tag.name) FROM post_tag WHERE post_tag.post_id = post.id AND post_tag.name IN (SELECT value FROM json_each(?9))
+                    ) >= CASE WHEN ?10 THEN json_array_length(?9) ELSE 1 END)
+                    GROUP BY post.id
+                    HAVING (?12 IS NULL OR post.published_date >= ?12) AND (?13 IS NULL OR post.published_date < ?13)
+                    AND (?14 IS NULL OR post.updated_date >= ?14) AND (?15 IS NULL OR post.updated_date < ?15)
+                    AND (?4 IS NULL OR CASE WHEN ?11 THEN post.published_date ELSE post.updated_date END < ?4 OR (CASE WHEN ?11 THEN post.published_date ELSE post.updated_date END = ?4 AND post.id < ?5))
+                    ORDER BY CASE WHEN ?11 THEN post.published_date ELSE post.updated_date END DESC, post.id DESC
+                    LIMIT ?6
+                ",
+                author_icon = icons::author_summary_icon_query("author.id"), channel_icon = icons::channel_summary_icon_query("channel.id"),
+                post_tables = listings::POST_LISTING_TABLES,
+                post_conditions = listings::POST_LISTING_CONDITIONS,
+                post_labels = labels::POST_LABELS_COLUMN,
+                exclude_labels = labels::exclude_labels_condition("?2", "?3"),
+            )
         )?;
 
-        let mut rows = stmt.query([])?;
+        let mut rows = stmt.query((
+            &lang, exclude_all_labels, &exclude_labels, page.cursor_key(), cursor_post_id, page.query_limit(),
+            &channels, &authors, &tags, all_tags, sort_by_published,
+            published_after, published_before, updated_after, updated_before,
+        ))?;
         let mut posts = Vec::new();
 
         while let Some(row) = rows.next()? {
@@ -51,19 +153,37 @@ pub async fn api_post_list(
             let channel_handle: String = row.get(7)?;
             let channel_name: String = row.get(8)?;
             let channel_lang: String = row.get(9)?;
+            let slug: Option<String> = row.get(10)?;
+            let author_icon_sha256: Option<String> = row.get(11)?;
+            let channel_icon_sha256: Option<String> = row.get(12)?;
+            let lang: String = row.get(13)?;
+            let featured: bool = row.get(14)?;
+            let post_labels: Option<String> = row.get(15)?;
+            let content_warning: Option<String> = row.get(16)?;
+            let post_id: u32 = row.get(17)?;
+            let published_date: u64 = row.get(18)?;
+            let updated_date: u64 = row.get(19)?;
 
-            let author = AuthorSummary::new(&author_uuid, &author_name);
-            let channel = ChannelSummary::new(&channel_uuid, &channel_handle, &channel_name, &channel_lang);
-            posts.push(serde_json::json!({
+            let author = AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref());
+            let channel = ChannelSummary::new(&channel_uuid, &channel_handle, &channel_name, &channel_lang, channel_icon_sha256.as_deref());
+            let sort_date = if sort_by_published { published_date } else { updated_date };
+            posts.push((Cursor::new(sort_date, (post_id, sort.to_string())), serde_json::json!({
                 "post_uuid": post_uuid,
+                "slug": slug,
                 "revision_uuid": revision_uuid,
                 "revision_date": revision_date,
+                "published_date": published_date,
+                "updated_date": updated_date,
                 "title": title,
                 "author": author,
                 "channel": channel,
-            }));
+                "lang": lang,
+                "featured": featured,
+                "labels": labels::split_labels(post_labels),
+                "content_warning": content_warning,
+            })));
         }
 
-        Ok(Json(serde_json::json!(posts)))
+        Ok(Json(page.into_response(posts)))
     }, ErrorReporting::Json).await
 }
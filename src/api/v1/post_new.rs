@@ -15,12 +15,20 @@ use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
 use crate::sys_time;
 use crate::limits;
+use crate::slugs;
+use crate::media;
+use crate::labels;
+use crate::icons;
 
 use crate::api::v1::types::{
+    validate_post_slug,
+    validate_license,
+    normalize_language_code,
     AuthorSummary,
     RevisionInfo,
     PostInfo,
     ChannelSummary,
+    PostVisibility,
 };
 
 
@@ -31,6 +39,26 @@ struct MsgPostNew {
     title: String,
     text: String,
     tags: Vec<String>,
+    slug: Option<String>,
+
+    /// The channel's license is used if omitted.
+    #[serde(default)]
+    license: Option<String>,
+
+    /// The channel's language is used if omitted.
+    #[serde(default)]
+    lang: Option<String>,
+
+    #[serde(default)]
+    labels: Vec<String>,
+
+    /// Free text shown before the post, e.g. describing what the labels are about.
+    #[serde(default)]
+    content_warning: Option<String>,
+
+    /// `public` if omitted.
+    #[serde(default)]
+    visibility: Option<String>,
 }
 
 pub async fn api_post_new(
@@ -41,7 +69,7 @@ pub async fn api_post_new(
         // verify message
         let public_key = msg.public_key()?.to_owned();
         let msg = msg.verify()?;
-        let msg = serde_json::from_slice::<MsgPostNew>(&msg)?;
+        let msg = serde_json::from_slice::<MsgPostNew>(msg)?;
 
         if msg.title.len() > limits::MAX_PAGE_TITLE_SIZE {
             return Err(anyhow::anyhow!("Title is too long"));
@@ -57,36 +85,55 @@ pub async fn api_post_new(
             }
         }
 
+        if let Some(slug) = &msg.slug {
+            validate_post_slug(slug)?;
+        }
+
+        if let Some(license) = &msg.license {
+            validate_license(license)?;
+        }
+
+        let lang = msg.lang.as_deref().map(normalize_language_code).transpose()?;
+
+        let content_warning = msg.content_warning.as_deref().filter(|content_warning| !content_warning.is_empty());
+        labels::validate_labels(&msg.labels, content_warning)?;
+
+        let visibility = msg.visibility.as_deref().map(str::parse::<PostVisibility>).transpose()?.unwrap_or(PostVisibility::Public);
+
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
-        let (author_id, author_uuid, author_name) = trx.query_row(
-            "SELECT author.id, author.uuid, author.name FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+        let (author_id, author_uuid, author_name, author_icon_sha256) = trx.query_row(
+            &format!("SELECT author.id, author.uuid, author.name, {author_icon} FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id", author_icon = icons::author_summary_icon_query("author.id")),
             [&public_key],
             |row| {
                 let author_id: u32 = row.get(0)?;
                 let author_uuid: String = row.get(1)?;
                 let author_name: String = row.get(2)?;
-                Ok((author_id, author_uuid, author_name))
+                let author_icon_sha256: Option<String> = row.get(3)?;
+                Ok((author_id, author_uuid, author_name, author_icon_sha256))
             }
         )?;
 
-        let (channel_id, channel_handle, channel_name, channel_lang) = trx.query_row(
-            "SELECT channel.id, channel.handle, channel.name, channel.language_code FROM channel, channel_author WHERE channel.uuid = ? AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?",
+        let (channel_id, channel_handle, channel_name, channel_lang, channel_icon_sha256, channel_license) = trx.query_row(
+            &format!("SELECT channel.id, channel.handle, channel.name, channel.language_code, {channel_icon}, channel.license FROM channel, channel_author WHERE channel.uuid = ? AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?", channel_icon = icons::channel_summary_icon_query("channel.id")),
             (&msg.channel_uuid, &author_id),
             |row| {
                 let channel_id: u32 = row.get(0)?;
                 let channel_handle: String = row.get(1)?;
                 let channel_name: String = row.get(2)?;
                 let channel_lang: String = row.get(3)?;
-                Ok((channel_id, channel_handle, channel_name, channel_lang))
+                let channel_icon_sha256: Option<String> = row.get(4)?;
+                let channel_license: String = row.get(5)?;
+                Ok((channel_id, channel_handle, channel_name, channel_lang, channel_icon_sha256, channel_license))
             }
         )?;
 
+        let created_date = sys_time::get_sys_time_in_secs();
         let post_uuid = uuid::Uuid::new_v4().to_string();
         trx.execute(
-            "INSERT INTO post (uuid, channel_id) VALUES (?, ?)",
-            (&post_uuid, &channel_id),
+            "INSERT INTO post (uuid, channel_id, license, language_code, content_warning, visibility, published_date, updated_date) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            (&post_uuid, &channel_id, &msg.license, &lang, content_warning, visibility.as_str(), &created_date, &created_date),
         )?;
 
         let post_id = trx.query_row(
@@ -105,19 +152,28 @@ pub async fn api_post_new(
             )?;
         }
 
-        let created_date = sys_time::get_sys_time_in_secs();
+        slugs::set_post_slug(&trx, post_id, channel_id, msg.slug.as_deref())?;
+        labels::set_post_labels(&trx, post_id, &msg.labels, false)?;
+        let post_labels = labels::get_post_labels(&trx, post_id)?;
+
         let revision_uuid = uuid::Uuid::new_v4().to_string();
         trx.execute(
             "INSERT INTO revision (uuid, post_id, author_id, created_date, title, revision_text) VALUES (?, ?, ?, ?, ?, ?)",
             (&revision_uuid, &post_id, &author_id, &created_date, &msg.title, &msg.text),
         )?;
 
+        media::record_post_media(&trx, post_id, &msg.text)?;
+
         trx.commit()?;
 
-        let author = AuthorSummary::new(&author_uuid, &author_name);
+        let author = AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref());
         let revision = RevisionInfo::new(&revision_uuid, &author, created_date, &msg.title, &msg.text);
-        let channel = ChannelSummary::new(&msg.channel_uuid, &channel_handle, &channel_name, &channel_lang);
-        let post = PostInfo::new(&post_uuid, &channel, msg.tags, &revision, &author);
+        let channel = ChannelSummary::new(&msg.channel_uuid, &channel_handle, &channel_name, &channel_lang, channel_icon_sha256.as_deref());
+        let mut post = PostInfo::new(&post_uuid, msg.slug.as_deref(), &channel, msg.tags, &revision, &author);
+        post.set_license(msg.license.as_deref().unwrap_or(&channel_license));
+        post.set_lang(lang.as_deref().unwrap_or(&channel_lang));
+        post.set_labels(post_labels, content_warning);
+        post.set_visibility(visibility);
 
         Ok(Json(post))
     }, ErrorReporting::Json).await
@@ -0,0 +1,71 @@
+
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+use monostate::MustBe;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::crypto::SignedMessage;
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+
+use crate::api::v1::types::is_html_format_requested;
+use crate::api::v1::post_info::{load_post_info, render_post_html};
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MsgPostRead {
+    command: MustBe!("post_read"),
+    uuid: String,
+
+    #[serde(default)]
+    format: Option<String>,
+}
+
+pub async fn api_post_read(
+    State(state): State<Arc<AppState>>,
+    Json(msg): Json<SignedMessage>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        // verify message
+        let public_key = msg.public_key()?.to_owned();
+        let msg = msg.verify()?;
+        let msg = serde_json::from_slice::<MsgPostRead>(msg)?;
+
+        let render_html = is_html_format_requested(msg.format.as_ref())?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let author_id = trx.query_row(
+            "SELECT author.id FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+            [&public_key],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                Ok(author_id)
+            }
+        )?;
+
+        // any author of the channel can read its posts, including private ones
+        trx.query_row(
+            "SELECT post.id FROM post, channel, channel_author WHERE post.uuid = ? AND post.is_deleted = 0 AND post.channel_id = channel.id AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?",
+            (&msg.uuid, author_id),
+            |row| row.get::<_, u32>(0),
+        ).map_err(|_| anyhow::anyhow!("Post not found"))?;
+
+        let mut post = load_post_info(&trx, &msg.uuid, true)?
+            .ok_or_else(|| anyhow::anyhow!("Post not found"))?;
+
+        if render_html {
+            let rendered_html = render_post_html(&state, &post);
+            post["rendered_html"] = serde_json::json!(rendered_html);
+        }
+
+        Ok(Json(post))
+    }, ErrorReporting::Json).await
+}
@@ -0,0 +1,81 @@
+
+use std::sync::Arc;
+use std::collections::HashMap;
+
+use hyper::StatusCode;
+use axum::{
+    extract::{State, Query},
+    response::IntoResponse,
+    Json,
+};
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::icons;
+
+use crate::api::v1::types::AuthorSummary;
+
+
+pub async fn api_post_revisions(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        let post_uuid = params.get("uuid").ok_or_else(|| anyhow::anyhow!("Missing uuid parameter"))?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        if trx.query_row(
+            "SELECT post.id FROM post, channel WHERE post.uuid = ? AND post.is_deleted = 0 AND post.visibility != 'private' AND post.channel_id = channel.id AND channel.is_deleted = 0",
+            [post_uuid],
+            |row| row.get::<_, u32>(0),
+        ).is_err() {
+            return Ok((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({
+                    "status": "not found",
+                })),
+            ).into_response());
+        }
+
+        let mut stmt = trx.prepare(
+            &format!(
+                "
+                    SELECT revision.uuid, revision.created_date, revision.title, author.uuid, author.name,
+                    {author_icon},
+                    revision.is_minor_edit
+                    FROM post, revision, author
+                    WHERE post.is_deleted = 0 AND revision.is_deleted = 0 AND author.is_deleted = 0
+                    AND post.uuid = ? AND post.id = revision.post_id AND revision.author_id = author.id
+                    ORDER BY revision.created_date DESC, revision.id DESC
+                    LIMIT 1000
+                ",
+                author_icon = icons::author_summary_icon_query("author.id"),
+            )
+        )?;
+
+        let mut rows = stmt.query([post_uuid])?;
+        let mut revisions = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let revision_uuid: String = row.get(0)?;
+            let revision_date: u64 = row.get(1)?;
+            let title: String = row.get(2)?;
+            let author_uuid: String = row.get(3)?;
+            let author_name: String = row.get(4)?;
+            let author_icon_sha256: Option<String> = row.get(5)?;
+            let minor_edit: bool = row.get(6)?;
+
+            revisions.push(serde_json::json!({
+                "revision_uuid": revision_uuid,
+                "revision_date": revision_date,
+                "title": title,
+                "author": AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref()),
+                "minor_edit": minor_edit,
+            }));
+        }
+
+        Ok(Json(serde_json::json!(revisions)).into_response())
+    }, ErrorReporting::Json).await
+}
@@ -13,14 +13,23 @@ use axum::{
 use crate::crypto::SignedMessage;
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::roles;
 use crate::sys_time;
 use crate::limits;
+use crate::slugs;
+use crate::media;
+use crate::labels;
+use crate::icons;
 
 use crate::api::v1::types::{
+    validate_post_slug,
+    validate_license,
+    normalize_language_code,
     AuthorSummary,
     RevisionInfo,
     PostInfo,
     ChannelSummary,
+    PostVisibility,
 };
 
 
@@ -31,6 +40,33 @@ struct MsgPostUpdate {
     title: String,
     text: String,
     tags: Vec<String>,
+
+    /// `None` keeps the current slug, and an empty string removes it.
+    slug: Option<String>,
+
+    /// `None` keeps the current license, and an empty string reverts to the channel's license.
+    #[serde(default)]
+    license: Option<String>,
+
+    /// `None` keeps the current language, and an empty string reverts to the channel's language.
+    #[serde(default)]
+    lang: Option<String>,
+
+    /// `None` keeps the current labels. Labels applied by admins are always kept.
+    #[serde(default)]
+    labels: Option<Vec<String>>,
+
+    /// `None` keeps the current content warning, and an empty string removes it.
+    #[serde(default)]
+    content_warning: Option<String>,
+
+    /// `None` keeps the current visibility.
+    #[serde(default)]
+    visibility: Option<String>,
+
+    /// Minor edits (e.g. typo fixes) do not change the post's `updated_date`.
+    #[serde(default)]
+    minor_edit: bool,
 }
 
 pub async fn api_post_update(
@@ -41,7 +77,7 @@ pub async fn api_post_update(
         // verify message
         let public_key = msg.public_key()?.to_owned();
         let msg = msg.verify()?;
-        let msg = serde_json::from_slice::<MsgPostUpdate>(&msg)?;
+        let msg = serde_json::from_slice::<MsgPostUpdate>(msg)?;
 
         if msg.title.len() > limits::MAX_PAGE_TITLE_SIZE {
             return Err(anyhow::anyhow!("Title is too long"));
@@ -57,22 +93,41 @@ pub async fn api_post_update(
             }
         }
 
+        if let Some(slug) = msg.slug.as_deref().filter(|slug| !slug.is_empty()) {
+            validate_post_slug(slug)?;
+        }
+
+        if let Some(license) = msg.license.as_deref().filter(|license| !license.is_empty()) {
+            validate_license(license)?;
+        }
+
+        let lang = match msg.lang.as_deref() {
+            Some("") => Some(None),
+            Some(lang) => Some(Some(normalize_language_code(lang)?)),
+            None => None,
+        };
+
+        labels::validate_labels(msg.labels.as_deref().unwrap_or_default(), msg.content_warning.as_deref())?;
+
+        let visibility = msg.visibility.as_deref().map(str::parse::<PostVisibility>).transpose()?;
+
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
-        let (author_id, author_uuid, author_name) = trx.query_row(
-            "SELECT author.id, author.uuid, author.name FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+        let (author_id, author_uuid, author_name, author_icon_sha256) = trx.query_row(
+            &format!("SELECT author.id, author.uuid, author.name, {author_icon} FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id", author_icon = icons::author_summary_icon_query("author.id")),
             [&public_key],
             |row| {
                 let author_id: u32 = row.get(0)?;
                 let author_uuid: String = row.get(1)?;
                 let author_name: String = row.get(2)?;
-                Ok((author_id, author_uuid, author_name))
+                let author_icon_sha256: Option<String> = row.get(3)?;
+                Ok((author_id, author_uuid, author_name, author_icon_sha256))
             }
         )?;
 
-        let (_channel_id, channel_handle, channel_name, channel_lang, post_id, channel_uuid) = trx.query_row(
-            "SELECT channel.id, channel.handle, channel.name, channel.language_code, post.id, channel.uuid FROM post, channel, channel_author WHERE post.uuid = ? AND post.is_deleted = 0 AND post.channel_id = channel.id AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?",
+        let (channel_id, channel_handle, channel_name, channel_lang, post_id, channel_uuid, channel_icon_sha256) = trx.query_row(
+            &format!("SELECT channel.id, channel.handle, channel.name, channel.language_code, post.id, channel.uuid, {channel_icon} FROM post, channel, channel_author WHERE post.uuid = ? AND post.is_deleted = 0 AND post.channel_id = channel.id AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?", channel_icon = icons::channel_summary_icon_query("channel.id")),
             (&msg.uuid, &author_id),
             |row| {
                 let channel_id: u32 = row.get(0)?;
@@ -81,10 +136,13 @@ pub async fn api_post_update(
                 let channel_lang: String = row.get(3)?;
                 let post_id: u32 = row.get(4)?;
                 let channel_uuid: String = row.get(5)?;
-                Ok((channel_id, channel_handle, channel_name, channel_lang, post_id, channel_uuid))
+                let channel_icon_sha256: Option<String> = row.get(6)?;
+                Ok((channel_id, channel_handle, channel_name, channel_lang, post_id, channel_uuid, channel_icon_sha256))
             }
         )?;
 
+        roles::check_post_editable(&trx, channel_id, post_id, author_id)?;
+
         let mut stmt = trx.prepare("SELECT name FROM post_tag WHERE post_id = ?")?;
         let mut rows = stmt.query([&post_id])?;
 
@@ -125,19 +183,85 @@ pub async fn api_post_update(
             )?;
         }
 
+        if let Some(slug) = &msg.slug {
+            let slug = if slug.is_empty() { None } else { Some(slug.as_str()) };
+            slugs::set_post_slug(&trx, post_id, channel_id, slug)?;
+        }
+        let slug = slugs::get_post_slug(&trx, post_id)?;
+
+        if let Some(license) = &msg.license {
+            let license = if license.is_empty() { None } else { Some(license.as_str()) };
+            trx.execute(
+                "UPDATE post SET license = ? WHERE id = ?",
+                (license, post_id),
+            )?;
+        }
+        if let Some(lang) = &lang {
+            trx.execute(
+                "UPDATE post SET language_code = ? WHERE id = ?",
+                (lang, post_id),
+            )?;
+        }
+        if let Some(post_labels) = &msg.labels {
+            labels::set_post_labels(&trx, post_id, post_labels, false)?;
+        }
+        if let Some(content_warning) = &msg.content_warning {
+            let content_warning = if content_warning.is_empty() { None } else { Some(content_warning.as_str()) };
+            trx.execute(
+                "UPDATE post SET content_warning = ? WHERE id = ?",
+                (content_warning, post_id),
+            )?;
+        }
+        if let Some(visibility) = visibility {
+            trx.execute(
+                "UPDATE post SET visibility = ? WHERE id = ?",
+                (visibility.as_str(), post_id),
+            )?;
+        }
+
         let created_date = sys_time::get_sys_time_in_secs();
+        if !msg.minor_edit {
+            trx.execute(
+                "UPDATE post SET updated_date = ? WHERE id = ?",
+                (created_date, post_id),
+            )?;
+        }
+
+        let post_labels = labels::get_post_labels(&trx, post_id)?;
+        let (license, lang, content_warning, visibility, published_date, updated_date) = trx.query_row(
+            "SELECT COALESCE(post.license, channel.license), COALESCE(post.language_code, channel.language_code), post.content_warning, post.visibility, post.published_date, post.updated_date FROM post, channel WHERE post.id = ? AND post.channel_id = channel.id",
+            [post_id],
+            |row| {
+                let license: String = row.get(0)?;
+                let lang: String = row.get(1)?;
+                let content_warning: Option<String> = row.get(2)?;
+                let visibility: String = row.get(3)?;
+                let published_date: u64 = row.get(4)?;
+                let updated_date: u64 = row.get(5)?;
+                Ok((license, lang, content_warning, visibility, published_date, updated_date))
+            },
+        )?;
+
         let revision_uuid = uuid::Uuid::new_v4().to_string();
         trx.execute(
-            "INSERT INTO revision (uuid, post_id, author_id, created_date, title, revision_text) VALUES (?, ?, ?, ?, ?, ?)",
-            (&revision_uuid, &post_id, &author_id, &created_date, &msg.title, &msg.text),
+            "INSERT INTO revision (uuid, post_id, author_id, created_date, is_minor_edit, title, revision_text) VALUES (?, ?, ?, ?, ?, ?, ?)",
+            (&revision_uuid, &post_id, &author_id, &created_date, msg.minor_edit, &msg.title, &msg.text),
         )?;
 
+        media::record_post_media(&trx, post_id, &msg.text)?;
+
         trx.commit()?;
 
-        let author = AuthorSummary::new(&author_uuid, &author_name);
+        let author = AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref());
         let revision = RevisionInfo::new(&revision_uuid, &author, created_date, &msg.title, &msg.text);
-        let channel = ChannelSummary::new(&channel_uuid, &channel_handle, &channel_name, &channel_lang);
-        let post = PostInfo::new(&msg.uuid, &channel, msg.tags, &revision, &author);
+        let channel = ChannelSummary::new(&channel_uuid, &channel_handle, &channel_name, &channel_lang, channel_icon_sha256.as_deref());
+        let mut post = PostInfo::new(&msg.uuid, slug.as_deref(), &channel, msg.tags, &revision, &author);
+        post.set_license(&license);
+        post.set_lang(&lang);
+        post.set_labels(post_labels, content_warning.as_deref());
+        post.set_visibility(visibility.parse()?);
+        post.set_published_date(published_date);
+        post.set_updated_date(updated_date);
 
         Ok(Json(post))
     }, ErrorReporting::Json).await
@@ -0,0 +1,87 @@
+
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+use monostate::MustBe;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::crypto::SignedMessage;
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::roles;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MsgRevisionDelete {
+    command: MustBe!("revision_delete"),
+    uuid: String,
+}
+
+pub async fn api_revision_delete(
+    State(state): State<Arc<AppState>>,
+    Json(msg): Json<SignedMessage>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        // verify message
+        let public_key = msg.public_key()?.to_owned();
+        let msg = msg.verify()?;
+        let msg = serde_json::from_slice::<MsgRevisionDelete>(msg)?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let author_id = trx.query_row(
+            "SELECT author.id FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+            [&public_key],
+            |row| {
+                let author_id: u32 = row.get(0)?;
+                Ok(author_id)
+            }
+        )?;
+
+        let (revision_id, post_id, channel_id) = trx.query_row(
+            "SELECT revision.id, post.id, channel.id FROM revision, post, channel, channel_author WHERE revision.uuid = ? AND revision.is_deleted = 0 AND revision.post_id = post.id AND post.is_deleted = 0 AND post.channel_id = channel.id AND channel.is_deleted = 0 AND channel.id = channel_author.channel_id AND channel_author.author_id = ?",
+            (&msg.uuid, &author_id),
+            |row| {
+                let revision_id: u32 = row.get(0)?;
+                let post_id: u32 = row.get(1)?;
+                let channel_id: u32 = row.get(2)?;
+                Ok((revision_id, post_id, channel_id))
+            }
+        ).map_err(|_| anyhow::anyhow!("Revision not found"))?;
+
+        roles::check_post_editable(&trx, channel_id, post_id, author_id)?;
+
+        // the previous revision becomes the current version of the post
+        let revision_count: u32 = trx.query_row(
+            "SELECT COUNT(*) FROM revision WHERE post_id = ? AND is_deleted = 0",
+            [post_id],
+            |row| row.get(0),
+        )?;
+        if revision_count <= 1 {
+            return Err(anyhow::anyhow!("Cannot delete the only revision of a post"));
+        }
+
+        trx.execute(
+            "UPDATE revision SET is_deleted = 1 WHERE id = ?",
+            (&revision_id,),
+        )?;
+
+        // the publication date is kept, but the last update may have been deleted
+        trx.execute(
+            "UPDATE post SET updated_date = COALESCE((SELECT MAX(created_date) FROM revision WHERE post_id = post.id AND is_deleted = 0 AND is_minor_edit = 0), published_date) WHERE id = ?",
+            (&post_id,),
+        )?;
+
+        trx.commit()?;
+
+        Ok(Json(serde_json::json!({
+            "status": "ok",
+        })))
+    }, ErrorReporting::Json).await
+}
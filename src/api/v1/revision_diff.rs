@@ -0,0 +1,99 @@
+
+use std::sync::Arc;
+use std::collections::HashMap;
+
+use hyper::StatusCode;
+use axum::{
+    extract::{State, Query},
+    response::IntoResponse,
+    Json,
+};
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::icons;
+
+use crate::api::v1::types::{
+    AuthorSummary,
+    RevisionInfo,
+    TextDiffInfo,
+};
+
+
+/// Returns the post's uuid and the revision.
+fn get_revision(conn: &rusqlite::Connection, revision_uuid: &str) -> rusqlite::Result<(String, RevisionInfo)> {
+    conn.query_row(
+        &format!(
+            "
+                SELECT post.uuid, revision.created_date, revision.title, revision.revision_text, author.uuid, author.name,
+                {author_icon}
+                FROM channel, post, revision, author
+                WHERE channel.is_deleted = 0 AND post.is_deleted = 0 AND post.visibility != 'private' AND revision.is_deleted = 0 AND author.is_deleted = 0 AND revision.uuid = ? AND post.channel_id = channel.id AND post.id = revision.post_id AND revision.author_id = author.id
+            ",
+            author_icon = icons::author_summary_icon_query("author.id"),
+        ),
+        [revision_uuid],
+        |row| {
+            let post_uuid: String = row.get(0)?;
+            let created_date: u64 = row.get(1)?;
+            let title: String = row.get(2)?;
+            let revision_text: String = row.get(3)?;
+            let author_uuid: String = row.get(4)?;
+            let author_name: String = row.get(5)?;
+            let author_icon_sha256: Option<String> = row.get(6)?;
+
+            let author = AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref());
+            Ok((post_uuid, RevisionInfo::new(revision_uuid, &author, created_date, &title, &revision_text)))
+        }
+    )
+}
+
+pub async fn api_revision_diff(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        let from_uuid = params.get("from").ok_or_else(|| anyhow::anyhow!("Missing from parameter"))?;
+        let to_uuid = params.get("to").ok_or_else(|| anyhow::anyhow!("Missing to parameter"))?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let ((post_uuid, from), (to_post_uuid, to)) = match (get_revision(&trx, from_uuid), get_revision(&trx, to_uuid)) {
+            (Ok(from), Ok(to)) => (from, to),
+            _ => {
+                return Ok((
+                    StatusCode::NOT_FOUND,
+                    Json(serde_json::json!({
+                        "status": "not found",
+                    })),
+                ).into_response());
+            },
+        };
+
+        if post_uuid != to_post_uuid {
+            return Err(anyhow::anyhow!("Revisions belong to different posts"));
+        }
+
+        let title_diff = TextDiffInfo::new(from.uuid(), to.uuid(), from.title(), to.title());
+        let text_diff = TextDiffInfo::new(from.uuid(), to.uuid(), from.revision_text(), to.revision_text());
+
+        Ok(Json(serde_json::json!({
+            "post_uuid": post_uuid,
+            "from": {
+                "revision_uuid": from.uuid(),
+                "revision_date": from.created_date(),
+                "title": from.title(),
+                "author": from.author(),
+            },
+            "to": {
+                "revision_uuid": to.uuid(),
+                "revision_date": to.created_date(),
+                "title": to.title(),
+                "author": to.author(),
+            },
+            "title": title_diff,
+            "text": text_diff,
+        })).into_response())
+    }, ErrorReporting::Json).await
+}
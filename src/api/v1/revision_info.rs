@@ -0,0 +1,127 @@
+
+use std::sync::Arc;
+use std::collections::HashMap;
+
+use hyper::StatusCode;
+use axum::{
+    extract::{State, Query},
+    response::IntoResponse,
+    Json,
+};
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::icons;
+
+use crate::api::v1::types::{
+    is_html_format_requested,
+    ChannelSummary,
+    AuthorSummary,
+};
+
+
+pub async fn api_revision_info(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        let revision_uuid = params.get("uuid").ok_or_else(|| anyhow::anyhow!("Missing uuid parameter"))?;
+        let render_html = is_html_format_requested(params.get("format"))?;
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
+        let (
+            post_uuid,
+            channel_uuid,
+            channel_handle,
+            channel_name,
+            channel_lang,
+            revision_date,
+            title,
+            author_uuid,
+            author_name,
+            revision_text,
+            author_icon_sha256,
+            channel_icon_sha256,
+            minor_edit,
+            published_date,
+        ) = if let Ok(values) = trx.query_row(
+            &format!(
+                "
+                    SELECT post.uuid, channel.uuid, channel.handle, channel.name, channel.language_code, revision.created_date, revision.title, author.uuid, author.name, revision.revision_text,
+                    {author_icon},
+                    {channel_icon},
+                    revision.is_minor_edit, post.published_date
+                    FROM channel, post, revision, author
+                    WHERE channel.is_deleted = 0 AND post.is_deleted = 0 AND post.visibility != 'private' AND revision.is_deleted = 0 AND author.is_deleted = 0 AND revision.uuid = ? AND post.channel_id = channel.id AND post.id = revision.post_id AND revision.author_id = author.id
+                ",
+                author_icon = icons::author_summary_icon_query("author.id"), channel_icon = icons::channel_summary_icon_query("channel.id"),
+            ),
+            [revision_uuid],
+            |row| {
+                let post_uuid: String = row.get(0)?;
+                let channel_uuid: String = row.get(1)?;
+                let channel_handle: String = row.get(2)?;
+                let channel_name: String = row.get(3)?;
+                let channel_lang: String = row.get(4)?;
+                let revision_date: u64 = row.get(5)?;
+                let title: String = row.get(6)?;
+                let author_uuid: String = row.get(7)?;
+                let author_name: String = row.get(8)?;
+                let revision_text: String = row.get(9)?;
+                let author_icon_sha256: Option<String> = row.get(10)?;
+                let channel_icon_sha256: Option<String> = row.get(11)?;
+                let minor_edit: bool = row.get(12)?;
+                let published_date: u64 = row.get(13)?;
+                Ok((
+                    post_uuid,
+                    channel_uuid,
+                    channel_handle,
+                    channel_name,
+                    channel_lang,
+                    revision_date,
+                    title,
+                    author_uuid,
+                    author_name,
+                    revision_text,
+                    author_icon_sha256,
+                    channel_icon_sha256,
+                    minor_edit,
+                    published_date,
+                ))
+            }
+        ) {
+            values
+        } else {
+            return Ok((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({
+                    "status": "not found",
+                })),
+            ).into_response());
+        };
+
+        let channel = ChannelSummary::new(&channel_uuid, &channel_handle, &channel_name, &channel_lang, channel_icon_sha256.as_deref());
+        let author = AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref());
+
+        let mut revision = serde_json::json!({
+            "post_uuid": post_uuid,
+            "channel": channel,
+            "revision_uuid": revision_uuid,
+            "revision_date": revision_date,
+            "published_date": published_date,
+            "minor_edit": minor_edit,
+            "title": title,
+            "revision_text": revision_text,
+            "author": author,
+        });
+
+        if render_html {
+            let rendered_html = state.markdown_cache.lock().unwrap().render(revision_uuid, &revision_text);
+            revision["rendered_html"] = serde_json::json!(rendered_html);
+        }
+
+        Ok(Json(revision).into_response())
+    }, ErrorReporting::Json).await
+}
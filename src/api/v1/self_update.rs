@@ -16,6 +16,7 @@ use crate::error_reporting::{ErrorReporting, result_into_response};
 
 use crate::api::v1::types::AuthorInfo;
 use crate::limits;
+use crate::icons;
 
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +24,10 @@ struct MsgSelfUpdate {
     command: MustBe!("self_update"),
     name: String,
     description_text: String,
+
+    /// SHA-256 hash of an uploaded image. Empty string removes the icon.
+    #[serde(default)]
+    icon_sha256: Option<String>,
 }
 
 pub async fn api_self_update(
@@ -33,10 +38,7 @@ pub async fn api_self_update(
         // verify message
         let public_key = msg.public_key()?.to_owned();
         let msg = msg.verify()?;
-        let msg = serde_json::from_slice::<MsgSelfUpdate>(&msg)?;
-
-        let mut db_connection = state.db_connection.lock().unwrap();
-        let trx = db_connection.transaction()?;
+        let msg = serde_json::from_slice::<MsgSelfUpdate>(msg)?;
 
         if msg.description_text.len() > limits::MAX_ITEM_DESCRIPTION_SIZE {
             return Err(anyhow::anyhow!("Description text is too long"));
@@ -46,8 +48,32 @@ pub async fn api_self_update(
             return Err(anyhow::anyhow!("Name is too long"));
         }
 
+        // only registered authors get their images decoded
+        {
+            let mut db_connection = state.db_connection.lock().unwrap();
+            let trx = db_connection.transaction()?;
+            trx.query_row(
+                "SELECT author.id FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+                [&public_key],
+                |_| Ok(()),
+            )?;
+        }
+
+        // images are processed without holding the database lock, on a blocking thread
+        let icons = match msg.icon_sha256.as_deref() {
+            Some(icon_sha256) if !icon_sha256.is_empty() => {
+                let media_store = state.media_store.clone();
+                let icon_sha256 = icon_sha256.to_string();
+                Some(tokio::task::spawn_blocking(move || icons::load_and_render_icons(&media_store, &icon_sha256)).await??)
+            },
+            _ => None,
+        };
+
+        let mut db_connection = state.db_connection.lock().unwrap();
+        let trx = db_connection.transaction()?;
+
         let (author_id, author_uuid, created_date) = trx.query_row(
-            "SELECT author.id, author.uuid, author.created_date FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
+            "SELECT author.id, author.uuid, author.registered_date FROM author, author_public_key WHERE author_public_key.public_key = ? AND author.is_deleted = 0 AND author.id = author_public_key.author_id",
             [&public_key],
             |row| {
                 let author_id: u32 = row.get(0)?;
@@ -60,9 +86,26 @@ pub async fn api_self_update(
             "UPDATE author SET name = ?, description_text = ? WHERE id = ?",
             (&msg.name, &msg.description_text, author_id),
         )?;
+
+        match (msg.icon_sha256.as_deref(), icons) {
+            (Some(icon_sha256), Some(icons)) => {
+                if trx.query_row("SELECT id FROM media WHERE sha256 = ?", [icon_sha256], |_| Ok(())).is_err() {
+                    return Err(anyhow::anyhow!("Icon file not found"));
+                }
+                let media_ids = icons::store_icons(&trx, &state.media_store, author_id, &icons)?;
+                icons::set_author_icons(&trx, author_id, &media_ids)?;
+            },
+            (Some(_), None) => {
+                icons::set_author_icons(&trx, author_id, &[])?;
+            },
+            _ => {},
+        }
+
+        let author_icons = icons::get_author_icons(&trx, author_id)?;
         trx.commit()?;
 
-        let author = AuthorInfo::new(&author_uuid, &msg.name, created_date, &msg.description_text);
+        let mut author = AuthorInfo::new(&author_uuid, &msg.name, created_date, &msg.description_text);
+        author.set_icons(author_icons);
 
         Ok(Json(author))
     }, ErrorReporting::Json).await
@@ -0,0 +1,53 @@
+
+use std::sync::Arc;
+use std::collections::HashMap;
+
+use hyper::StatusCode;
+use axum::{
+    extract::{State, Query},
+    http::header,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::sitemaps;
+
+
+pub async fn api_sitemap(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        let page = match params.get("page") {
+            Some(page) => page.parse::<u32>().ok().filter(|page| *page > 0).ok_or_else(|| anyhow::anyhow!("Invalid page"))?,
+            None => 1,
+        };
+
+        let urls = {
+            let mut db_connection = state.db_connection.lock().unwrap();
+            let trx = db_connection.transaction()?;
+
+            sitemaps::load_sitemap(&trx, &state.frontend, page)?
+        };
+
+        // the first sitemap always exists, even when empty
+        if urls.is_empty() && page > 1 {
+            return Ok((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({
+                    "status": "not found",
+                })),
+            ).into_response());
+        }
+
+        Ok((
+            [
+                (header::CONTENT_TYPE, "application/xml; charset=utf-8"),
+                (header::CACHE_CONTROL, sitemaps::SITEMAP_CACHE_CONTROL),
+            ],
+            sitemaps::render_sitemap(&urls),
+        ).into_response())
+    }, ErrorReporting::Json).await
+}
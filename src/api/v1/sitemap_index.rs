@@ -0,0 +1,34 @@
+
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::header,
+    response::IntoResponse,
+};
+
+use crate::state::AppState;
+use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::sitemaps;
+
+
+pub async fn api_sitemap_index(
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    result_into_response(async move {
+        let dates = {
+            let mut db_connection = state.db_connection.lock().unwrap();
+            let trx = db_connection.transaction()?;
+
+            sitemaps::load_sitemap_dates(&trx)?
+        };
+
+        Ok((
+            [
+                (header::CONTENT_TYPE, "application/xml; charset=utf-8"),
+                (header::CACHE_CONTROL, sitemaps::SITEMAP_CACHE_CONTROL),
+            ],
+            sitemaps::render_sitemap_index(&state.frontend, &dates),
+        ).into_response())
+    }, ErrorReporting::Json).await
+}
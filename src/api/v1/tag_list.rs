@@ -10,13 +10,17 @@ use axum::{
 
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::pagination::{Cursor, PageParams};
 
 
 pub async fn api_tag_list(
     State(state): State<Arc<AppState>>,
-    Query(_params): Query<HashMap<String, String>>,
+    Query(params): Query<HashMap<String, String>>,
 ) -> impl IntoResponse {
     result_into_response(async move {
+        // tags are ordered by their number of posts, then by name ascending
+        let page = PageParams::<String>::from_query(&params)?;
+
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
@@ -24,27 +28,28 @@ pub async fn api_tag_list(
             "
                 SELECT post_tag.name, COUNT(post.id)
                 FROM channel, post, post_tag
-                WHERE channel.is_deleted = 0 AND post.is_deleted = 0
+                WHERE channel.is_deleted = 0 AND post.is_deleted = 0 AND post.visibility = 'public'
                 AND channel.id = post.channel_id AND post_tag.post_id = post.id
                 GROUP BY post_tag.name
-                ORDER BY COUNT(post.id) DESC
-                LIMIT 1000
+                HAVING ?1 IS NULL OR COUNT(post.id) < ?1 OR (COUNT(post.id) = ?1 AND post_tag.name > ?2)
+                ORDER BY COUNT(post.id) DESC, post_tag.name ASC
+                LIMIT ?3
             "
         )?;
 
-        let mut rows = stmt.query([])?;
+        let mut rows = stmt.query((page.cursor_key(), page.cursor_id(), page.query_limit()))?;
         let mut tags = Vec::new();
 
         while let Some(row) = rows.next()? {
             let tag_name: String = row.get(0)?;
             let page_count: u64 = row.get(1)?;
 
-            tags.push(serde_json::json!({
+            tags.push((Cursor::new(page_count, tag_name.clone()), serde_json::json!({
                 "tag_name": tag_name,
                 "page_count": page_count,
-            }));
+            })));
         }
 
-        Ok(Json(serde_json::json!(tags)))
+        Ok(Json(page.into_response(tags)))
     }, ErrorReporting::Json).await
 }
@@ -10,8 +10,13 @@ use axum::{
 
 use crate::state::AppState;
 use crate::error_reporting::{ErrorReporting, result_into_response};
+use crate::labels;
+use crate::listings;
+use crate::pagination::{Cursor, PageParams};
+use crate::icons;
 
 use crate::api::v1::types::{
+    normalize_language_code,
     AuthorSummary,
     ChannelSummary,
 };
@@ -27,23 +32,45 @@ pub async fn api_tag_posts(
             None => return Err(anyhow::anyhow!("tag_name parameter is required")),
         };
 
+        // posts in the given language or one of its sublanguages, e.g. `en` matches `en-US`
+        let lang = params.get("lang").map(|lang| normalize_language_code(lang)).transpose()?;
+
+        // posts with any of the labels, or with any label or content warning if `all`
+        let (exclude_all_labels, exclude_labels) = labels::parse_exclude_labels(params.get("exclude_labels"))?;
+
+        let page = PageParams::<u32>::from_query(&params)?;
+
         let mut db_connection = state.db_connection.lock().unwrap();
         let trx = db_connection.transaction()?;
 
         let mut stmt = trx.prepare(
-            "
-                SELECT DISTINCT post.uuid, revision.uuid, revision.created_date, revision.title, author.uuid, author.name, channel.uuid, channel.handle, channel.name, channel.language_code
-                FROM channel, post, revision, author, post_tag
-                WHERE channel.is_deleted = 0 AND post.is_deleted = 0 AND revision.is_deleted = 0 AND author.is_deleted = 0 
-                AND channel.id = post.channel_id AND post.id = revision.post_id AND revision.author_id = author.id AND post_tag.post_id = post.id
-                AND post_tag.name = ?
-                GROUP BY post.id
-                ORDER BY revision.created_date DESC
-                LIMIT 1000
-            "
+            &format!(
+                "
+                    SELECT DISTINCT post.uuid, revision.uuid, MAX(revision.created_date), revision.title, author.uuid, author.name, channel.uuid, channel.handle, channel.name, channel.language_code, post_slug.slug,
+                    {author_icon},
+                    {channel_icon},
+                    COALESCE(post.language_code, channel.language_code),
+                    {post_labels}, post.content_warning, post.id, post.published_date, post.updated_date
+                    FROM {post_tables}, post_tag
+                    WHERE {post_conditions}
+                    AND post_tag.post_id = post.id
+                    AND post_tag.name = ?1
+                    AND (?2 IS NULL OR COALESCE(post.language_code, channel.language_code) = ?2 OR COALESCE(post.language_code, channel.language_code) LIKE ?2 || '-%')
+                    AND {exclude_labels}
+                    GROUP BY post.id
+                    HAVING ?5 IS NULL OR post.updated_date < ?5 OR (post.updated_date = ?5 AND post.id < ?6)
+                    ORDER BY post.updated_date DESC, post.id DESC
+                    LIMIT ?7
+                ",
+                author_icon = icons::author_summary_icon_query("author.id"), channel_icon = icons::channel_summary_icon_query("channel.id"),
+                post_tables = listings::POST_LISTING_TABLES,
+                post_conditions = listings::POST_LISTING_CONDITIONS,
+                post_labels = labels::POST_LABELS_COLUMN,
+                exclude_labels = labels::exclude_labels_condition("?3", "?4"),
+            )
         )?;
 
-        let mut rows = stmt.query([tag_name])?;
+        let mut rows = stmt.query((tag_name, &lang, exclude_all_labels, &exclude_labels, page.cursor_key(), page.cursor_id(), page.query_limit()))?;
         let mut posts = Vec::new();
 
         while let Some(row) = rows.next()? {
@@ -57,19 +84,34 @@ pub async fn api_tag_posts(
             let channel_handle: String = row.get(7)?;
             let channel_name: String = row.get(8)?;
             let channel_lang: String = row.get(9)?;
+            let slug: Option<String> = row.get(10)?;
+            let author_icon_sha256: Option<String> = row.get(11)?;
+            let channel_icon_sha256: Option<String> = row.get(12)?;
+            let lang: String = row.get(13)?;
+            let post_labels: Option<String> = row.get(14)?;
+            let content_warning: Option<String> = row.get(15)?;
+            let post_id: u32 = row.get(16)?;
+            let published_date: u64 = row.get(17)?;
+            let updated_date: u64 = row.get(18)?;
 
-            let author = AuthorSummary::new(&author_uuid, &author_name);
-            let channel = ChannelSummary::new(&channel_uuid, &channel_handle, &channel_name, &channel_lang);
-            posts.push(serde_json::json!({
+            let author = AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref());
+            let channel = ChannelSummary::new(&channel_uuid, &channel_handle, &channel_name, &channel_lang, channel_icon_sha256.as_deref());
+            posts.push((Cursor::new(updated_date, post_id), serde_json::json!({
                 "post_uuid": post_uuid,
+                "slug": slug,
                 "revision_uuid": revision_uuid,
                 "revision_date": revision_date,
+                "published_date": published_date,
+                "updated_date": updated_date,
                 "title": title,
                 "author": author,
                 "channel": channel,
-            }));
+                "lang": lang,
+                "labels": labels::split_labels(post_labels),
+                "content_warning": content_warning,
+            })));
         }
 
-        Ok(Json(serde_json::json!(posts)))
+        Ok(Json(page.into_response(posts)))
     }, ErrorReporting::Json).await
 }
@@ -1,6 +1,9 @@
 
 use serde::{Serialize, Deserialize};
 
+use crate::media::MEDIA_URL_PREFIX;
+use super::IconImage;
+
 
 /// AuthorInfo is a struct that contains detailed information about an author.
 /// It is for example returned by `/api/v1/author/info`.
@@ -10,6 +13,12 @@ pub struct AuthorInfo {
     name: String,
     created_date: u64,
     description_text: String,
+
+    #[serde(default)]
+    icons: Vec<IconImage>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rendered_html: Option<String>,
 }
 
 impl AuthorInfo {
@@ -19,6 +28,8 @@ impl AuthorInfo {
             name: name.to_string(),
             created_date,
             description_text: description_text.to_string(),
+            icons: Vec::new(),
+            rendered_html: None,
         }
     }
 
@@ -37,6 +48,22 @@ impl AuthorInfo {
     pub fn description_text(&self) -> &str {
         &self.description_text
     }
+
+    pub fn icons(&self) -> &[IconImage] {
+        &self.icons
+    }
+
+    pub fn set_icons(&mut self, icons: Vec<IconImage>) {
+        self.icons = icons;
+    }
+
+    pub fn rendered_html(&self) -> Option<&str> {
+        self.rendered_html.as_deref()
+    }
+
+    pub fn set_rendered_html(&mut self, rendered_html: &str) {
+        self.rendered_html = Some(rendered_html.to_string());
+    }
 }
 
 
@@ -46,13 +73,15 @@ impl AuthorInfo {
 pub struct AuthorSummary {
     uuid: String,
     name: String,
+    icon_url: Option<String>,
 }
 
 impl AuthorSummary {
-    pub fn new(uuid: &str, name: &str) -> AuthorSummary {
+    pub fn new(uuid: &str, name: &str, icon_sha256: Option<&str>) -> AuthorSummary {
         AuthorSummary {
             uuid: uuid.to_string(),
             name: name.to_string(),
+            icon_url: icon_sha256.map(|sha256| format!("{}{}", MEDIA_URL_PREFIX, sha256)),
         }
     }
 
@@ -63,4 +92,8 @@ impl AuthorSummary {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn icon_url(&self) -> Option<&str> {
+        self.icon_url.as_deref()
+    }
 }
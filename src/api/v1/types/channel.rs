@@ -1,6 +1,11 @@
 
 use serde::{Serialize, Deserialize};
 
+use crate::media::MEDIA_URL_PREFIX;
+use super::IconImage;
+use super::AuthorSummary;
+use super::DEFAULT_LICENSE;
+
 use crate::api::v1::types::is_valid_dns_token;
 
 
@@ -21,6 +26,14 @@ pub struct ChannelInfo {
     created_date: u64,
     lang: String,
     description_text: String,
+    license: String,
+    comments_enabled: bool,
+
+    #[serde(default)]
+    icons: Vec<IconImage>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rendered_html: Option<String>,
 }
 
 impl ChannelInfo {
@@ -32,6 +45,10 @@ impl ChannelInfo {
             created_date,
             lang: lang.to_string(),
             description_text: description_text.to_string(),
+            license: DEFAULT_LICENSE.to_string(),
+            comments_enabled: true,
+            icons: Vec::new(),
+            rendered_html: None,
         }
     }
 
@@ -58,6 +75,38 @@ impl ChannelInfo {
     pub fn description_text(&self) -> &str {
         &self.description_text
     }
+
+    pub fn license(&self) -> &str {
+        &self.license
+    }
+
+    pub fn set_license(&mut self, license: &str) {
+        self.license = license.to_string();
+    }
+
+    pub fn comments_enabled(&self) -> bool {
+        self.comments_enabled
+    }
+
+    pub fn set_comments_enabled(&mut self, comments_enabled: bool) {
+        self.comments_enabled = comments_enabled;
+    }
+
+    pub fn icons(&self) -> &[IconImage] {
+        &self.icons
+    }
+
+    pub fn set_icons(&mut self, icons: Vec<IconImage>) {
+        self.icons = icons;
+    }
+
+    pub fn rendered_html(&self) -> Option<&str> {
+        self.rendered_html.as_deref()
+    }
+
+    pub fn set_rendered_html(&mut self, rendered_html: &str) {
+        self.rendered_html = Some(rendered_html.to_string());
+    }
 }
 
 
@@ -69,15 +118,17 @@ pub struct ChannelSummary {
     handle: String,
     name: String,
     lang: String,
+    icon_url: Option<String>,
 }
 
 impl ChannelSummary {
-    pub fn new(uuid: &str, handle: &str, name: &str, lang: &str) -> ChannelSummary {
+    pub fn new(uuid: &str, handle: &str, name: &str, lang: &str, icon_sha256: Option<&str>) -> ChannelSummary {
         ChannelSummary {
             uuid: uuid.to_string(),
             handle: handle.to_string(),
             name: name.to_string(),
             lang: lang.to_string(),
+            icon_url: icon_sha256.map(|sha256| format!("{}{}", MEDIA_URL_PREFIX, sha256)),
         }
     }
 
@@ -96,4 +147,77 @@ impl ChannelSummary {
     pub fn lang(&self) -> &str {
         &self.lang
     }
+
+    pub fn icon_url(&self) -> Option<&str> {
+        self.icon_url.as_deref()
+    }
+}
+
+
+/// ChannelRole is the role of an author in a channel.
+/// Owners manage the channel, editors edit any post, and contributors only their own posts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelRole {
+    Owner,
+    Editor,
+    Contributor,
+}
+
+impl ChannelRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChannelRole::Owner => "owner",
+            ChannelRole::Editor => "editor",
+            ChannelRole::Contributor => "contributor",
+        }
+    }
+
+    pub fn can_manage_channel(&self) -> bool {
+        *self == ChannelRole::Owner
+    }
+
+    pub fn can_edit_all_posts(&self) -> bool {
+        *self == ChannelRole::Owner || *self == ChannelRole::Editor
+    }
+}
+
+impl std::str::FromStr for ChannelRole {
+    type Err = anyhow::Error;
+
+    fn from_str(role: &str) -> Result<Self, Self::Err> {
+        match role {
+            "owner" => Ok(ChannelRole::Owner),
+            "editor" => Ok(ChannelRole::Editor),
+            "contributor" => Ok(ChannelRole::Contributor),
+            _ => Err(anyhow::anyhow!("Invalid role")),
+        }
+    }
+}
+
+
+/// ChannelAuthor is an author of a channel with their role.
+/// It is for example returned by `/api/v1/channel/authors`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelAuthor {
+    #[serde(flatten)]
+    author: AuthorSummary,
+    role: ChannelRole,
+}
+
+impl ChannelAuthor {
+    pub fn new(author: &AuthorSummary, role: ChannelRole) -> ChannelAuthor {
+        ChannelAuthor {
+            author: author.clone(),
+            role,
+        }
+    }
+
+    pub fn author(&self) -> &AuthorSummary {
+        &self.author
+    }
+
+    pub fn role(&self) -> ChannelRole {
+        self.role
+    }
 }
@@ -0,0 +1,72 @@
+
+use serde::{Serialize, Deserialize};
+
+use crate::api::v1::types::AuthorSummary;
+
+
+/// CommentInfo is a struct that contains information about a comment on a post.
+/// Deleted comments keep their place in threads, without the author and the text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentInfo {
+    uuid: String,
+    post_uuid: String,
+    parent_uuid: Option<String>,
+    author: Option<AuthorSummary>,
+    created_date: u64,
+    is_deleted: bool,
+    comment_text: String,
+}
+
+impl CommentInfo {
+    pub fn new(uuid: &str, post_uuid: &str, parent_uuid: Option<&str>, author: &AuthorSummary, created_date: u64, comment_text: &str) -> CommentInfo {
+        CommentInfo {
+            uuid: uuid.to_string(),
+            post_uuid: post_uuid.to_string(),
+            parent_uuid: parent_uuid.map(|parent_uuid| parent_uuid.to_string()),
+            author: Some(author.clone()),
+            created_date,
+            is_deleted: false,
+            comment_text: comment_text.to_string(),
+        }
+    }
+
+    pub fn new_deleted(uuid: &str, post_uuid: &str, parent_uuid: Option<&str>, created_date: u64) -> CommentInfo {
+        CommentInfo {
+            uuid: uuid.to_string(),
+            post_uuid: post_uuid.to_string(),
+            parent_uuid: parent_uuid.map(|parent_uuid| parent_uuid.to_string()),
+            author: None,
+            created_date,
+            is_deleted: true,
+            comment_text: String::new(),
+        }
+    }
+
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+
+    pub fn post_uuid(&self) -> &str {
+        &self.post_uuid
+    }
+
+    pub fn parent_uuid(&self) -> Option<&str> {
+        self.parent_uuid.as_deref()
+    }
+
+    pub fn author(&self) -> Option<&AuthorSummary> {
+        self.author.as_ref()
+    }
+
+    pub fn created_date(&self) -> u64 {
+        self.created_date
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.is_deleted
+    }
+
+    pub fn comment_text(&self) -> &str {
+        &self.comment_text
+    }
+}
@@ -0,0 +1,166 @@
+
+use serde::{Serialize, Deserialize};
+use similar::{ChangeTag, TextDiff};
+
+
+/// Number of unchanged lines shown around each change.
+const DIFF_CONTEXT_RADIUS: usize = 3;
+
+/// DiffSegment is a part of a line in a diff.
+/// Emphasized segments are the words that actually changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffSegment {
+    emphasized: bool,
+    value: String,
+}
+
+impl DiffSegment {
+    pub fn emphasized(&self) -> bool {
+        self.emphasized
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+
+/// DiffLine is a line in a diff hunk. Line numbers start from 1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffLine {
+    tag: String,
+    old_line: Option<usize>,
+    new_line: Option<usize>,
+    segments: Vec<DiffSegment>,
+}
+
+impl DiffLine {
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    pub fn old_line(&self) -> Option<usize> {
+        self.old_line
+    }
+
+    pub fn new_line(&self) -> Option<usize> {
+        self.new_line
+    }
+
+    pub fn segments(&self) -> &Vec<DiffSegment> {
+        &self.segments
+    }
+}
+
+
+/// DiffHunk is a group of changed lines with their surrounding context.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffHunk {
+    old_start: usize,
+    old_lines: usize,
+    new_start: usize,
+    new_lines: usize,
+    lines: Vec<DiffLine>,
+}
+
+impl DiffHunk {
+    pub fn old_start(&self) -> usize {
+        self.old_start
+    }
+
+    pub fn old_lines(&self) -> usize {
+        self.old_lines
+    }
+
+    pub fn new_start(&self) -> usize {
+        self.new_start
+    }
+
+    pub fn new_lines(&self) -> usize {
+        self.new_lines
+    }
+
+    pub fn lines(&self) -> &Vec<DiffLine> {
+        &self.lines
+    }
+}
+
+
+/// TextDiffInfo is a line- and word-level diff between two texts.
+/// It contains both structured hunks and the same diff in unified format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextDiffInfo {
+    hunks: Vec<DiffHunk>,
+    unified: String,
+}
+
+impl TextDiffInfo {
+    pub fn new(old_name: &str, new_name: &str, old_text: &str, new_text: &str) -> TextDiffInfo {
+        let diff = TextDiff::from_lines(old_text, new_text);
+
+        let mut hunks = Vec::new();
+        for group in diff.grouped_ops(DIFF_CONTEXT_RADIUS) {
+            let (first, last) = match (group.first(), group.last()) {
+                (Some(first), Some(last)) => (first, last),
+                _ => continue,
+            };
+
+            let old_start = first.old_range().start;
+            let old_end = last.old_range().end;
+            let new_start = first.new_range().start;
+            let new_end = last.new_range().end;
+
+            let mut lines = Vec::new();
+            for op in &group {
+                for change in diff.iter_inline_changes(op) {
+                    let tag = match change.tag() {
+                        ChangeTag::Equal => "equal",
+                        ChangeTag::Delete => "delete",
+                        ChangeTag::Insert => "insert",
+                    };
+
+                    let segments = change.iter_strings_lossy().map(|(emphasized, value)| {
+                        DiffSegment {
+                            emphasized,
+                            value: value.into_owned(),
+                        }
+                    }).collect();
+
+                    lines.push(DiffLine {
+                        tag: tag.to_string(),
+                        old_line: change.old_index().map(|index| index + 1),
+                        new_line: change.new_index().map(|index| index + 1),
+                        segments,
+                    });
+                }
+            }
+
+            hunks.push(DiffHunk {
+                old_start: old_start + 1,
+                old_lines: old_end - old_start,
+                new_start: new_start + 1,
+                new_lines: new_end - new_start,
+                lines,
+            });
+        }
+
+        let unified = diff
+            .unified_diff()
+            .context_radius(DIFF_CONTEXT_RADIUS)
+            .header(old_name, new_name)
+            .to_string();
+
+        TextDiffInfo {
+            hunks,
+            unified,
+        }
+    }
+
+    pub fn hunks(&self) -> &Vec<DiffHunk> {
+        &self.hunks
+    }
+
+    pub fn unified(&self) -> &str {
+        &self.unified
+    }
+}
@@ -0,0 +1,109 @@
+
+use serde::{Serialize, Deserialize};
+
+use crate::api::v1::types::{
+    ChannelSummary,
+    AuthorSummary,
+};
+
+
+/// Settings of the post that a draft applies when it is published.
+/// As with `post/update`, `None` keeps the post's current setting (or the default for new posts),
+/// and an empty string reverts the slug, license, language or content warning to the default.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DraftPostSettings {
+    #[serde(default)]
+    pub slug: Option<String>,
+
+    #[serde(default)]
+    pub license: Option<String>,
+
+    #[serde(default)]
+    pub lang: Option<String>,
+
+    #[serde(default)]
+    pub labels: Option<Vec<String>>,
+
+    #[serde(default)]
+    pub content_warning: Option<String>,
+
+    #[serde(default)]
+    pub visibility: Option<String>,
+}
+
+/// DraftInfo is a struct that contains detailed information about a draft.
+/// Drafts are only visible to the authors of their channels.
+/// `post_uuid` is `None` for drafts of new posts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DraftInfo {
+    uuid: String,
+    channel: ChannelSummary,
+    post_uuid: Option<String>,
+    author: AuthorSummary,
+    updated_date: u64,
+    publish_at: Option<u64>,
+    title: String,
+    text: String,
+    tags: Vec<String>,
+
+    #[serde(flatten)]
+    post_settings: DraftPostSettings,
+}
+
+impl DraftInfo {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(uuid: &str, channel: &ChannelSummary, post_uuid: Option<&str>, author: &AuthorSummary, updated_date: u64, publish_at: Option<u64>, title: &str, text: &str, tags: Vec<String>, post_settings: DraftPostSettings) -> DraftInfo {
+        DraftInfo {
+            uuid: uuid.to_string(),
+            channel: channel.clone(),
+            post_uuid: post_uuid.map(|post_uuid| post_uuid.to_string()),
+            author: author.clone(),
+            updated_date,
+            publish_at,
+            title: title.to_string(),
+            text: text.to_string(),
+            tags,
+            post_settings,
+        }
+    }
+
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+
+    pub fn channel(&self) -> &ChannelSummary {
+        &self.channel
+    }
+
+    pub fn post_uuid(&self) -> Option<&str> {
+        self.post_uuid.as_deref()
+    }
+
+    pub fn author(&self) -> &AuthorSummary {
+        &self.author
+    }
+
+    pub fn updated_date(&self) -> u64 {
+        self.updated_date
+    }
+
+    pub fn publish_at(&self) -> Option<u64> {
+        self.publish_at
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn tags(&self) -> &Vec<String> {
+        &self.tags
+    }
+
+    pub fn post_settings(&self) -> &DraftPostSettings {
+        &self.post_settings
+    }
+}
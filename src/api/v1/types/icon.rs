@@ -0,0 +1,29 @@
+
+use serde::{Serialize, Deserialize};
+
+use crate::media::MEDIA_URL_PREFIX;
+
+
+/// IconImage is a square rendition of an author avatar or a channel icon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IconImage {
+    size: u32,
+    url: String,
+}
+
+impl IconImage {
+    pub fn new(size: u32, sha256: &str) -> IconImage {
+        IconImage {
+            size,
+            url: format!("{}{}", MEDIA_URL_PREFIX, sha256),
+        }
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
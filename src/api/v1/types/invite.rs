@@ -8,6 +8,12 @@ pub struct Invite {
     uuid: String,
 }
 
+impl Default for Invite {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Invite {
     pub fn new() -> Invite {
         let uuid = uuid::Uuid::new_v4().to_string();
@@ -0,0 +1,20 @@
+
+/// Content labels that can be applied to posts.
+/// The frontend decides how to present labelled posts (e.g. behind a click-through).
+pub static KNOWN_CONTENT_LABELS: &[&str] = &[
+    "violence",
+    "self-harm",
+    "sexual",
+    "nudity",
+    "drugs",
+    "hate",
+    "graphic",
+    "spoilers",
+];
+
+pub fn validate_content_label(label: &str) -> Result<(), anyhow::Error> {
+    if !KNOWN_CONTENT_LABELS.contains(&label) {
+        return Err(anyhow::anyhow!("Unknown content label"));
+    }
+    Ok(())
+}
@@ -0,0 +1,114 @@
+
+/// Parses a BCP 47 (RFC 5646) language tag and returns it with the
+/// conventional casing: `zh-Hant-TW`, `en-US`, `sr-Latn-RS-x-private`.
+/// Grandfathered tags are not accepted.
+pub fn normalize_language_code(lang: &str) -> Result<String, anyhow::Error> {
+    let invalid = || anyhow::anyhow!("Invalid language code");
+
+    if lang.is_empty() || lang.len() > 64 {
+        return Err(invalid());
+    }
+    let subtags: Vec<&str> = lang.split('-').collect();
+    if subtags.iter().any(|subtag| subtag.is_empty() || subtag.len() > 8 || !subtag.chars().all(|c| c.is_ascii_alphanumeric())) {
+        return Err(invalid());
+    }
+
+    let is_alpha = |s: &str| s.chars().all(|c| c.is_ascii_alphabetic());
+    let is_digit = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+
+    let mut normalized: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    // language (2-3 letters with up to 3 extlangs, or 4-8 letters)
+    // A tag consisting only of a private use part is also allowed.
+    let language = subtags[0];
+    if language.eq_ignore_ascii_case("x") {
+        // handled by the private use section below
+    } else if is_alpha(language) && (2..=8).contains(&language.len()) {
+        normalized.push(language.to_ascii_lowercase());
+        i += 1;
+        if language.len() <= 3 {
+            let mut extlangs = 0;
+            while extlangs < 3 && i < subtags.len() && subtags[i].len() == 3 && is_alpha(subtags[i]) {
+                normalized.push(subtags[i].to_ascii_lowercase());
+                extlangs += 1;
+                i += 1;
+            }
+        }
+
+        // script
+        if i < subtags.len() && subtags[i].len() == 4 && is_alpha(subtags[i]) {
+            let script = subtags[i].to_ascii_lowercase();
+            normalized.push(script[..1].to_ascii_uppercase() + &script[1..]);
+            i += 1;
+        }
+
+        // region
+        if i < subtags.len() && ((subtags[i].len() == 2 && is_alpha(subtags[i])) || (subtags[i].len() == 3 && is_digit(subtags[i]))) {
+            normalized.push(subtags[i].to_ascii_uppercase());
+            i += 1;
+        }
+
+        // variants
+        let mut variants = Vec::new();
+        while i < subtags.len() {
+            let subtag = subtags[i];
+            let is_variant = subtag.len() >= 5 || (subtag.len() == 4 && subtag.as_bytes()[0].is_ascii_digit());
+            if !is_variant {
+                break;
+            }
+            let variant = subtag.to_ascii_lowercase();
+            if variants.contains(&variant) {
+                return Err(invalid());
+            }
+            variants.push(variant.clone());
+            normalized.push(variant);
+            i += 1;
+        }
+
+        // extensions
+        let mut singletons = Vec::new();
+        while i < subtags.len() && subtags[i].len() == 1 && !subtags[i].eq_ignore_ascii_case("x") {
+            let singleton = subtags[i].to_ascii_lowercase();
+            if singletons.contains(&singleton) {
+                return Err(invalid());
+            }
+            singletons.push(singleton.clone());
+            normalized.push(singleton);
+            i += 1;
+
+            let start = i;
+            while i < subtags.len() && subtags[i].len() >= 2 {
+                normalized.push(subtags[i].to_ascii_lowercase());
+                i += 1;
+            }
+            if i == start {
+                return Err(invalid());
+            }
+        }
+    } else {
+        return Err(invalid());
+    }
+
+    // private use
+    if i < subtags.len() && subtags[i].eq_ignore_ascii_case("x") {
+        normalized.push("x".to_string());
+        i += 1;
+        if i == subtags.len() {
+            return Err(invalid());
+        }
+        while i < subtags.len() {
+            normalized.push(subtags[i].to_ascii_lowercase());
+            i += 1;
+        }
+    }
+
+    if i != subtags.len() {
+        return Err(invalid());
+    }
+    Ok(normalized.join("-"))
+}
+
+pub fn validate_language_code(lang: &str) -> Result<(), anyhow::Error> {
+    normalize_language_code(lang).map(|_| ())
+}
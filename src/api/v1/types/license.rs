@@ -0,0 +1,26 @@
+
+/// Licenses accepted for channels and posts, as SPDX identifiers.
+/// `LicenseRef-All-Rights-Reserved` is used for works not licensed to the public.
+pub static KNOWN_LICENSES: &[&str] = &[
+    "LicenseRef-All-Rights-Reserved",
+    "CC0-1.0",
+    "CC-BY-4.0",
+    "CC-BY-SA-4.0",
+    "CC-BY-NC-4.0",
+    "CC-BY-NC-SA-4.0",
+    "CC-BY-ND-4.0",
+    "CC-BY-NC-ND-4.0",
+    "GFDL-1.3-or-later",
+    "MIT",
+    "Apache-2.0",
+];
+
+/// License of channels that do not specify one.
+pub static DEFAULT_LICENSE: &str = "LicenseRef-All-Rights-Reserved";
+
+pub fn validate_license(license: &str) -> Result<(), anyhow::Error> {
+    if !KNOWN_LICENSES.contains(&license) {
+        return Err(anyhow::anyhow!("Unknown license"));
+    }
+    Ok(())
+}
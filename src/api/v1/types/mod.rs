@@ -3,6 +3,13 @@ mod invite;
 mod author;
 mod channel;
 mod post;
+mod diff;
+mod draft;
+mod icon;
+mod comment;
+mod license;
+mod language;
+mod label;
 
 
 pub use invite::Invite;
@@ -13,19 +20,48 @@ pub use author::AuthorSummary;
 pub use channel::validate_channel_handle;
 pub use channel::ChannelInfo;
 pub use channel::ChannelSummary;
+pub use channel::ChannelRole;
+pub use channel::ChannelAuthor;
 
+pub use post::validate_post_slug;
 pub use post::RevisionInfo;
 pub use post::PostInfo;
+pub use post::PostTranslation;
+pub use post::PostVisibility;
+
+pub use diff::DiffSegment;
+pub use diff::DiffLine;
+pub use diff::DiffHunk;
+pub use diff::TextDiffInfo;
+
+pub use draft::DraftInfo;
+pub use draft::DraftPostSettings;
+
+pub use icon::IconImage;
+
+pub use comment::CommentInfo;
+
+pub use license::KNOWN_LICENSES;
+pub use license::DEFAULT_LICENSE;
+pub use license::validate_license;
+
+pub use language::normalize_language_code;
+pub use language::validate_language_code;
+
+pub use label::KNOWN_CONTENT_LABELS;
+pub use label::validate_content_label;
 
 
 use regex::Regex;
 
-pub fn validate_language_code(lang: &str) -> Result<(), anyhow::Error> {
-    let re = Regex::new(r"^[a-z]{2, 3}(-[A-Z]{2})?$").unwrap();
-    if !re.is_match(lang) {
-        return Err(anyhow::anyhow!("Invalid language code"));
+/// Parses the `format` query parameter of endpoints returning Markdown texts.
+/// Returns true if rendered HTML is requested.
+pub fn is_html_format_requested(format: Option<&String>) -> Result<bool, anyhow::Error> {
+    match format.map(|format| format.as_str()) {
+        None | Some("markdown") => Ok(false),
+        Some("html") => Ok(true),
+        Some(_) => Err(anyhow::anyhow!("Invalid format")),
     }
-    Ok(())
 }
 
 pub fn is_valid_dns_token(token: &str) -> bool {
@@ -2,11 +2,55 @@
 use serde::{Serialize, Deserialize};
 
 use crate::api::v1::types::{
+    is_valid_dns_token,
     ChannelSummary,
     AuthorSummary,
+    DEFAULT_LICENSE,
 };
 
 
+pub fn validate_post_slug(slug: &str) -> Result<(), anyhow::Error> {
+    if !is_valid_dns_token(slug) {
+        return Err(anyhow::anyhow!("Invalid slug"));
+    }
+    Ok(())
+}
+
+
+/// PostVisibility controls where a post can be seen.
+/// Unlisted posts are only reachable by their uuid (or slug), and private posts only by the channel's authors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostVisibility {
+    Public,
+    Unlisted,
+    Private,
+}
+
+impl PostVisibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostVisibility::Public => "public",
+            PostVisibility::Unlisted => "unlisted",
+            PostVisibility::Private => "private",
+        }
+    }
+}
+
+impl std::str::FromStr for PostVisibility {
+    type Err = anyhow::Error;
+
+    fn from_str(visibility: &str) -> Result<Self, Self::Err> {
+        match visibility {
+            "public" => Ok(PostVisibility::Public),
+            "unlisted" => Ok(PostVisibility::Unlisted),
+            "private" => Ok(PostVisibility::Private),
+            _ => Err(anyhow::anyhow!("Invalid visibility")),
+        }
+    }
+}
+
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RevisionInfo {
     uuid: String,
@@ -54,26 +98,54 @@ impl RevisionInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PostInfo {
     post_uuid: String,
+    slug: Option<String>,
     channel: ChannelSummary,
     tags: Vec<String>,
     revision_uuid: String,
     revision_date: u64,
+
+    /// Date of the first revision, kept when later revisions are deleted.
+    published_date: u64,
+
+    /// Date of the latest revision that is not a minor edit.
+    updated_date: u64,
+
     title: String,
     revision_text: String,
     author: AuthorSummary,
+
+    /// The post's own license, or the channel's license if the post does not override it.
+    license: String,
+
+    /// The post's own language, or the channel's language if the post does not override it.
+    lang: String,
+
+    /// Content labels applied by the authors or by admins.
+    labels: Vec<String>,
+    content_warning: Option<String>,
+
+    visibility: PostVisibility,
 }
 
 impl PostInfo {
-    pub fn new(uuid: &str, channel: &ChannelSummary, tags: Vec<String>, revision: &RevisionInfo, author: &AuthorSummary) -> PostInfo {
+    pub fn new(uuid: &str, slug: Option<&str>, channel: &ChannelSummary, tags: Vec<String>, revision: &RevisionInfo, author: &AuthorSummary) -> PostInfo {
         PostInfo {
             post_uuid: uuid.to_string(),
+            slug: slug.map(|slug| slug.to_string()),
             channel: channel.clone(),
             tags,
             revision_uuid: revision.uuid().to_string(),
             revision_date: revision.created_date(),
+            published_date: revision.created_date(),
+            updated_date: revision.created_date(),
             title: revision.title().to_string(),
             revision_text: revision.revision_text().to_string(),
             author: author.clone(),
+            license: DEFAULT_LICENSE.to_string(),
+            lang: channel.lang().to_string(),
+            labels: Vec::new(),
+            content_warning: None,
+            visibility: PostVisibility::Public,
         }
     }
 
@@ -81,6 +153,10 @@ impl PostInfo {
         &self.post_uuid
     }
 
+    pub fn slug(&self) -> Option<&str> {
+        self.slug.as_deref()
+    }
+
     pub fn channel(&self) -> &ChannelSummary {
         &self.channel
     }
@@ -97,6 +173,22 @@ impl PostInfo {
         self.revision_date
     }
 
+    pub fn published_date(&self) -> u64 {
+        self.published_date
+    }
+
+    pub fn set_published_date(&mut self, published_date: u64) {
+        self.published_date = published_date;
+    }
+
+    pub fn updated_date(&self) -> u64 {
+        self.updated_date
+    }
+
+    pub fn set_updated_date(&mut self, updated_date: u64) {
+        self.updated_date = updated_date;
+    }
+
     pub fn title(&self) -> &str {
         &self.title
     }
@@ -108,4 +200,85 @@ impl PostInfo {
     pub fn author(&self) -> &AuthorSummary {
         &self.author
     }
+
+    pub fn license(&self) -> &str {
+        &self.license
+    }
+
+    pub fn set_license(&mut self, license: &str) {
+        self.license = license.to_string();
+    }
+
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    pub fn set_lang(&mut self, lang: &str) {
+        self.lang = lang.to_string();
+    }
+
+    pub fn labels(&self) -> &Vec<String> {
+        &self.labels
+    }
+
+    pub fn content_warning(&self) -> Option<&str> {
+        self.content_warning.as_deref()
+    }
+
+    pub fn set_labels(&mut self, labels: Vec<String>, content_warning: Option<&str>) {
+        self.labels = labels;
+        self.content_warning = content_warning.map(|content_warning| content_warning.to_string());
+    }
+
+    pub fn visibility(&self) -> PostVisibility {
+        self.visibility
+    }
+
+    pub fn set_visibility(&mut self, visibility: PostVisibility) {
+        self.visibility = visibility;
+    }
+}
+
+
+/// PostTranslation is a post linked as a translation of another post.
+/// It is listed in `/api/v1/post/info`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostTranslation {
+    post_uuid: String,
+    slug: Option<String>,
+    channel: ChannelSummary,
+    title: String,
+    lang: String,
+}
+
+impl PostTranslation {
+    pub fn new(post_uuid: &str, slug: Option<&str>, channel: &ChannelSummary, title: &str, lang: &str) -> PostTranslation {
+        PostTranslation {
+            post_uuid: post_uuid.to_string(),
+            slug: slug.map(|slug| slug.to_string()),
+            channel: channel.clone(),
+            title: title.to_string(),
+            lang: lang.to_string(),
+        }
+    }
+
+    pub fn post_uuid(&self) -> &str {
+        &self.post_uuid
+    }
+
+    pub fn slug(&self) -> Option<&str> {
+        self.slug.as_deref()
+    }
+
+    pub fn channel(&self) -> &ChannelSummary {
+        &self.channel
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
 }
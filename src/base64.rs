@@ -11,7 +11,7 @@ pub fn decode(data: &str) -> Result<Vec<u8>, base64::DecodeError> {
     base64_engine.decode(data)
 }
 
-pub fn serialize<S: Serializer>(v: &Vec<u8>, s: S) -> Result<S::Ok, S::Error> {
+pub fn serialize<S: Serializer>(v: &[u8], s: S) -> Result<S::Ok, S::Error> {
     let base64 = encode(v);
     String::serialize(&base64, s)
 }
@@ -19,5 +19,5 @@ pub fn serialize<S: Serializer>(v: &Vec<u8>, s: S) -> Result<S::Ok, S::Error> {
 pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
     let base64 = String::deserialize(d)?;
     decode(&base64)
-        .map_err(|e| serde::de::Error::custom(e))
+        .map_err(serde::de::Error::custom)
 }
@@ -3,6 +3,7 @@ use std::env;
 use std::net::SocketAddr;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use hyper::{Request, StatusCode};
 use axum::{
@@ -12,6 +13,7 @@ use axum::{
     response::{IntoResponse, Redirect, Response},
     Json,
     middleware::Next,
+    extract::DefaultBodyLimit,
     http::header,
 };
 
@@ -24,6 +26,11 @@ use tower_http::cors::{
 
 use alarkhabil_server::state::{PrimarySecret, AppState};
 use alarkhabil_server::db::RusqliteConnection;
+use alarkhabil_server::markdown::MarkdownCache;
+use alarkhabil_server::drafts;
+use alarkhabil_server::media::{self, MediaStore};
+use alarkhabil_server::frontend::Frontend;
+use alarkhabil_server::limits;
 
 use alarkhabil_server::api;
 
@@ -31,6 +38,8 @@ use alarkhabil_server::api;
 // const
 static SQL_SCHEMA_SQLITE: &str = include_str!("../sql/schema-sqlite.sql");
 static URL_GITHUB: &str = "https://github.com/metastable-void/alarkhabil-server";
+// base64-encoded file inside signed JSON (twice encoded) with some room for the envelope
+static MEDIA_UPLOAD_BODY_LIMIT: usize = limits::MAX_MEDIA_SIZE * 2 + 4096;
 static RESPONSE_HEADER_CSP: &str = "default-src 'none'; base-uri 'none'; form-action 'none'; frame-ancestors 'none';";
 
 
@@ -79,7 +88,10 @@ async fn main() -> anyhow::Result<()> {
     let primary_secret = PrimarySecret::new_from_env();
     let state = Arc::new(AppState {
         db_connection: Mutex::new(db_connection),
-        primary_secret: primary_secret,
+        primary_secret,
+        markdown_cache: Mutex::new(MarkdownCache::new()),
+        media_store: MediaStore::new_from_env(),
+        frontend: Frontend::new_from_env(),
     });
 
     let cors = CorsLayer::new()
@@ -103,18 +115,42 @@ async fn main() -> anyhow::Result<()> {
         // Admin v1
         .route("/api/v1/admin/meta/update", post(api::v1::api_admin_meta_update))
         .route("/api/v1/admin/meta/delete", post(api::v1::api_admin_meta_delete))
+        .route("/api/v1/admin/meta/revisions", get(api::v1::api_admin_meta_revisions))
+        .route("/api/v1/admin/meta/revision_info", get(api::v1::api_admin_meta_revision_info))
+        .route("/api/v1/admin/meta/rollback", post(api::v1::api_admin_meta_rollback))
         .route("/api/v1/admin/author/delete", post(api::v1::api_admin_author_delete))
         .route("/api/v1/admin/channel/delete", post(api::v1::api_admin_channel_delete))
         .route("/api/v1/admin/post/delete", post(api::v1::api_admin_post_delete))
+        .route("/api/v1/admin/post/labels", post(api::v1::api_admin_post_labels))
+        .route("/api/v1/admin/featured/update", post(api::v1::api_admin_featured_update))
 
         // Author's endpoints v1
         .route("/api/v1/self/update", post(api::v1::api_self_update))
         .route("/api/v1/channel/new", post(api::v1::api_channel_new))
         .route("/api/v1/channel/update", post(api::v1::api_channel_update))
         .route("/api/v1/channel/delete", post(api::v1::api_channel_delete))
+        .route("/api/v1/channel/add_author", post(api::v1::api_channel_add_author))
+        .route("/api/v1/channel/remove_author", post(api::v1::api_channel_remove_author))
+        .route("/api/v1/channel/set_author_role", post(api::v1::api_channel_set_author_role))
+        .route("/api/v1/channel/transfer_offer", post(api::v1::api_channel_transfer_offer))
+        .route("/api/v1/channel/transfer_accept", post(api::v1::api_channel_transfer_accept))
+        .route("/api/v1/channel/set_pins", post(api::v1::api_channel_set_pins))
         .route("/api/v1/post/new", post(api::v1::api_post_new))
         .route("/api/v1/post/update", post(api::v1::api_post_update))
         .route("/api/v1/post/delete", post(api::v1::api_post_delete))
+        .route("/api/v1/post/link_translation", post(api::v1::api_post_link_translation))
+        .route("/api/v1/post/unlink_translation", post(api::v1::api_post_unlink_translation))
+        .route("/api/v1/post/read", post(api::v1::api_post_read))
+        .route("/api/v1/revision/delete", post(api::v1::api_revision_delete))
+        .route("/api/v1/draft/new", post(api::v1::api_draft_new))
+        .route("/api/v1/draft/update", post(api::v1::api_draft_update))
+        .route("/api/v1/draft/delete", post(api::v1::api_draft_delete))
+        .route("/api/v1/draft/publish", post(api::v1::api_draft_publish))
+        .route("/api/v1/draft/info", post(api::v1::api_draft_info))
+        .route("/api/v1/draft/list", post(api::v1::api_draft_list))
+        .route("/api/v1/media/upload", post(api::v1::api_media_upload).layer(DefaultBodyLimit::max(MEDIA_UPLOAD_BODY_LIMIT)))
+        .route("/api/v1/comment/new", post(api::v1::api_comment_new))
+        .route("/api/v1/comment/delete", post(api::v1::api_comment_delete))
 
         // Public endpoints v1
         .route("/api/v1/meta/info", get(api::v1::api_meta_info))
@@ -127,10 +163,23 @@ async fn main() -> anyhow::Result<()> {
         .route("/api/v1/channel/list", get(api::v1::api_channel_list))
         .route("/api/v1/channel/authors", get(api::v1::api_channel_authors))
         .route("/api/v1/channel/posts", get(api::v1::api_channel_posts))
+        .route("/api/v1/channel/pinned", get(api::v1::api_channel_pinned))
+        .route("/api/v1/channel/history", get(api::v1::api_channel_history))
         .route("/api/v1/post/info", get(api::v1::api_post_info))
         .route("/api/v1/post/list", get(api::v1::api_post_list))
+        .route("/api/v1/post/featured", get(api::v1::api_post_featured))
+        .route("/api/v1/post/revisions", get(api::v1::api_post_revisions))
+        .route("/api/v1/post/comments", get(api::v1::api_post_comments))
+        .route("/api/v1/revision/info", get(api::v1::api_revision_info))
+        .route("/api/v1/revision/diff", get(api::v1::api_revision_diff))
+        .route("/api/v1/media/:sha256", get(api::v1::api_media_file))
         .route("/api/v1/tag/list", get(api::v1::api_tag_list))
         .route("/api/v1/tag/posts", get(api::v1::api_tag_posts))
+        .route("/api/v1/feed/atom", get(api::v1::api_feed_atom))
+        .route("/api/v1/feed/rss", get(api::v1::api_feed_rss))
+        .route("/api/v1/feed/json", get(api::v1::api_feed_json))
+        .route("/api/v1/sitemap.xml", get(api::v1::api_sitemap))
+        .route("/api/v1/sitemap_index.xml", get(api::v1::api_sitemap_index))
 
         // 404 page
         .fallback(handler_404)
@@ -141,6 +190,34 @@ async fn main() -> anyhow::Result<()> {
         // Set state
         .with_state(state.clone());
 
+    // publish scheduled drafts
+    let scheduler_state = state.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(drafts::DRAFT_PUBLISHING_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            match drafts::publish_due_drafts(&scheduler_state) {
+                Ok(0) => {},
+                Ok(count) => log::info!("Published {} scheduled drafts", count),
+                Err(e) => log::error!("Failed to publish scheduled drafts: {}", e),
+            }
+        }
+    });
+
+    // delete unreferenced media files
+    let gc_state = state.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(media::MEDIA_GC_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            match media::collect_garbage(&gc_state) {
+                Ok(0) => {},
+                Ok(count) => log::info!("Deleted {} unreferenced media files", count),
+                Err(e) => log::error!("Failed to delete unreferenced media files: {}", e),
+            }
+        }
+    });
+
     // run server
     let server = Server::bind(&addr).serve(app.into_make_service());
 
@@ -4,3 +4,12 @@ mod private_key;
 
 pub use signed_message::SignedMessage;
 pub use private_key::PrivateKey;
+
+use subtle::ConstantTimeEq;
+
+
+/// Compares secrets (e.g. tokens) in constant time, so that the time taken does not tell
+/// how much of a guess is correct. Only the lengths may leak.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
@@ -29,7 +29,7 @@ pub struct SignedMessage {
 
 impl SignedMessage {
     pub fn try_new(algo: &str, public_key: &[u8], signature: &[u8], message: &[u8]) -> Result<SignedMessage, anyhow::Error> {
-        if algo == "hmac-sha256" && public_key.len() != 0 {
+        if algo == "hmac-sha256" && !public_key.is_empty() {
             return Err(anyhow::anyhow!("You must not provide public key for HMAC: {}", algo));
         }
 
@@ -114,7 +114,7 @@ impl SignedMessage {
         hmac.update(self.msg.as_slice());
         let signature = hmac.finalize();
 
-        if signature != CtOutput::new(GenericArray::from_slice(&self.sig).clone()) {
+        if signature != CtOutput::new(*GenericArray::from_slice(&self.sig)) {
             return Err(anyhow::anyhow!("Invalid signature"));
         }
 
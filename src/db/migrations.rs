@@ -0,0 +1,135 @@
+
+/// Changes to the schema of databases created by earlier versions, in order.
+/// The schema queries only create missing tables and indexes, so new columns
+/// of existing tables and other changes to them are made here.
+/// `PRAGMA user_version` holds the number of migrations applied to a database.
+/// Migrations are never edited once released; new ones are appended.
+pub static MIGRATIONS: &[&str] = &[
+    // 1: comments can be disabled per channel
+    "
+        ALTER TABLE channel ADD COLUMN comments_enabled INTEGER NOT NULL DEFAULT 1;
+    ",
+
+    // 2: channel roles; existing channel authors were all owners
+    "
+        ALTER TABLE channel_author ADD COLUMN role BLOB NOT NULL DEFAULT 'owner';
+    ",
+
+    // 3: licenses; posts default to their channel's license
+    "
+        ALTER TABLE channel ADD COLUMN license BLOB NOT NULL DEFAULT 'LicenseRef-All-Rights-Reserved';
+        ALTER TABLE post ADD COLUMN license BLOB;
+    ",
+
+    // 4: post languages; posts default to their channel's language
+    "
+        ALTER TABLE post ADD COLUMN language_code BLOB;
+    ",
+
+    // 5: meta page variants, revisions and soft deletion
+    // `page_name` was declared UNIQUE inline, which cannot be dropped, so the table is rebuilt.
+    // The current content of each page becomes its first revision (with a random v4 uuid).
+    "
+        CREATE TABLE `meta_page_new` (
+          id INTEGER PRIMARY KEY,
+          page_name BLOB NOT NULL,
+          language_code BLOB NOT NULL DEFAULT '',
+          title BLOB NOT NULL,
+          updated_date INTEGER NOT NULL,
+          page_text BLOB NOT NULL DEFAULT '',
+          is_deleted INTEGER NOT NULL DEFAULT 0
+        );
+
+        INSERT INTO meta_page_new (id, page_name, title, updated_date, page_text)
+        SELECT id, page_name, title, updated_date, page_text FROM meta_page;
+
+        DROP TABLE meta_page;
+        ALTER TABLE meta_page_new RENAME TO meta_page;
+
+        CREATE TABLE IF NOT EXISTS `meta_page_revision` (
+          id INTEGER PRIMARY KEY,
+          uuid BLOB UNIQUE NOT NULL,
+          meta_page_id INTEGER NOT NULL,
+          created_date INTEGER NOT NULL,
+          title BLOB NOT NULL,
+          page_text BLOB NOT NULL DEFAULT '',
+          FOREIGN KEY(meta_page_id) REFERENCES meta_page(id) ON DELETE CASCADE
+        );
+
+        INSERT INTO meta_page_revision (uuid, meta_page_id, created_date, title, page_text)
+        SELECT
+            lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4' || substr(lower(hex(randomblob(2))), 2) || '-'
+            || substr('89ab', 1 + abs(random()) % 4, 1) || substr(lower(hex(randomblob(2))), 2) || '-' || lower(hex(randomblob(6))),
+            id, updated_date, title, page_text
+        FROM meta_page;
+    ",
+
+    // 6: content warnings on posts
+    "
+        ALTER TABLE post ADD COLUMN content_warning BLOB;
+    ",
+
+    // 7: post visibility; existing posts stay public
+    "
+        ALTER TABLE post ADD COLUMN visibility BLOB NOT NULL DEFAULT 'public';
+    ",
+
+    // 8: publication and update dates of posts, and minor edits
+    // Existing revisions are not minor edits, so the dates span all of them.
+    "
+        ALTER TABLE revision ADD COLUMN is_minor_edit INTEGER NOT NULL DEFAULT 0;
+        ALTER TABLE post ADD COLUMN published_date INTEGER NOT NULL DEFAULT 0;
+        ALTER TABLE post ADD COLUMN updated_date INTEGER NOT NULL DEFAULT 0;
+
+        UPDATE post SET
+            published_date = COALESCE((SELECT MIN(created_date) FROM revision WHERE revision.post_id = post.id AND revision.is_deleted = 0), 0),
+            updated_date = COALESCE((SELECT MAX(created_date) FROM revision WHERE revision.post_id = post.id AND revision.is_deleted = 0), 0);
+    ",
+
+    // 9: post settings of drafts
+    // Databases created before drafts do not have the table yet, so it is created as it was first.
+    "
+        CREATE TABLE IF NOT EXISTS `draft` (
+          id INTEGER PRIMARY KEY,
+          uuid BLOB UNIQUE NOT NULL,
+          channel_id INTEGER NOT NULL,
+          post_id INTEGER,
+          author_id INTEGER NOT NULL,
+          updated_date INTEGER NOT NULL,
+          publish_date INTEGER,
+          title BLOB NOT NULL,
+          draft_text BLOB NOT NULL,
+          FOREIGN KEY(channel_id) REFERENCES channel(id) ON DELETE CASCADE,
+          FOREIGN KEY(post_id) REFERENCES post(id) ON DELETE CASCADE,
+          FOREIGN KEY(author_id) REFERENCES author(id) ON DELETE CASCADE
+        );
+
+        ALTER TABLE draft ADD COLUMN slug BLOB;
+        ALTER TABLE draft ADD COLUMN license BLOB;
+        ALTER TABLE draft ADD COLUMN language_code BLOB;
+        ALTER TABLE draft ADD COLUMN labels BLOB;
+        ALTER TABLE draft ADD COLUMN content_warning BLOB;
+        ALTER TABLE draft ADD COLUMN visibility BLOB;
+    ",
+];
+
+/// Brings the database up to date. Runs before the schema queries, so that they can
+/// refer to the columns added here (e.g. in indexes).
+/// New databases are created by the schema queries and skip all migrations.
+pub fn migrate(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    let is_new_database = !conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'author')",
+        [],
+        |row| row.get::<_, bool>(0),
+    )?;
+
+    if !is_new_database {
+        let version: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+            log::info!("Applying database migration {}", index + 1);
+            conn.execute_batch(migration)?;
+        }
+    }
+
+    conn.pragma_update(None, "user_version", MIGRATIONS.len())
+}
@@ -1,6 +1,8 @@
 
 use core::ops::Deref;
 
+mod migrations;
+
 
 #[derive(Debug)]
 pub struct RusqliteConnection {
@@ -19,16 +21,9 @@ impl RusqliteConnection {
     }
 
     /// Passing an empty db_path is equivalent to passing None.
+    /// Existing databases are migrated before the init queries run.
     pub fn open(db_path: Option<&str>, init_queries: Option<&str>) -> Result<RusqliteConnection, rusqlite::Error> {
-        let db_path = if let Some(db_path) = db_path {
-            if db_path.is_empty() {
-                None
-            } else {
-                Some(db_path)
-            }
-        } else {
-            None
-        };
+        let db_path = db_path.filter(|db_path| !db_path.is_empty());
 
         let mut conn = match db_path {
             Some(db_path) => rusqlite::Connection::open(db_path)?,
@@ -37,6 +32,7 @@ impl RusqliteConnection {
 
         if let Some(init_queries) = init_queries {
             let init_tx = conn.transaction()?;
+            migrations::migrate(&init_tx)?;
             init_tx.execute_batch(init_queries)?;
             init_tx.commit()?;
         }
@@ -44,7 +40,7 @@ impl RusqliteConnection {
         Ok(RusqliteConnection::new(conn))
     }
 
-    pub fn transaction(&mut self) -> rusqlite::Result<RusqliteTransaction> {
+    pub fn transaction(&mut self) -> rusqlite::Result<RusqliteTransaction<'_>> {
         let transaction = self.connection.transaction()?;
         Ok(RusqliteTransaction {
             transaction,
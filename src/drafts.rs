@@ -0,0 +1,344 @@
+
+use crate::state::AppState;
+use crate::sys_time;
+use crate::slugs;
+use crate::media;
+use crate::roles;
+use crate::labels;
+use crate::icons;
+
+use crate::api::v1::types::{
+    validate_post_slug,
+    validate_license,
+    normalize_language_code,
+    AuthorSummary,
+    ChannelSummary,
+    DraftInfo,
+    DraftPostSettings,
+    PostInfo,
+    PostVisibility,
+    RevisionInfo,
+};
+
+
+/// Interval at which scheduled drafts are checked for publishing.
+pub static DRAFT_PUBLISHING_INTERVAL_SECS: u64 = 30;
+
+fn get_draft_tags(conn: &rusqlite::Connection, draft_id: u32) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT name FROM draft_tag WHERE draft_id = ? ORDER BY id")?;
+    let mut rows = stmt.query([draft_id])?;
+
+    let mut tags = Vec::new();
+    while let Some(row) = rows.next()? {
+        let tag: String = row.get(0)?;
+        tags.push(tag);
+    }
+    Ok(tags)
+}
+
+/// Replaces the tags of a draft.
+pub fn set_draft_tags(conn: &rusqlite::Connection, draft_id: u32, tags: &[String]) -> rusqlite::Result<()> {
+    conn.execute("DELETE FROM draft_tag WHERE draft_id = ?", [draft_id])?;
+    for tag in tags {
+        conn.execute(
+            "INSERT INTO draft_tag (draft_id, name) VALUES (?, ?)",
+            (draft_id, tag),
+        )?;
+    }
+    Ok(())
+}
+
+/// Validates the post settings of a draft the same way `post/new` and `post/update` do,
+/// and normalizes the language code.
+pub fn validate_post_settings(settings: &mut DraftPostSettings) -> anyhow::Result<()> {
+    if let Some(slug) = settings.slug.as_deref().filter(|slug| !slug.is_empty()) {
+        validate_post_slug(slug)?;
+    }
+
+    if let Some(license) = settings.license.as_deref().filter(|license| !license.is_empty()) {
+        validate_license(license)?;
+    }
+
+    if let Some(lang) = settings.lang.as_deref().filter(|lang| !lang.is_empty()) {
+        let lang = normalize_language_code(lang)?;
+        settings.lang = Some(lang);
+    }
+
+    labels::validate_labels(settings.labels.as_deref().unwrap_or_default(), settings.content_warning.as_deref())?;
+
+    if let Some(visibility) = &settings.visibility {
+        visibility.parse::<PostVisibility>()?;
+    }
+
+    Ok(())
+}
+
+fn get_draft_post_settings(conn: &rusqlite::Connection, draft_id: u32) -> anyhow::Result<DraftPostSettings> {
+    let (slug, license, lang, post_labels, content_warning, visibility) = conn.query_row(
+        "SELECT slug, license, language_code, labels, content_warning, visibility FROM draft WHERE id = ?",
+        [draft_id],
+        |row| {
+            let slug: Option<String> = row.get(0)?;
+            let license: Option<String> = row.get(1)?;
+            let lang: Option<String> = row.get(2)?;
+            let post_labels: Option<String> = row.get(3)?;
+            let content_warning: Option<String> = row.get(4)?;
+            let visibility: Option<String> = row.get(5)?;
+            Ok((slug, license, lang, post_labels, content_warning, visibility))
+        }
+    )?;
+
+    let labels = post_labels.as_deref().map(serde_json::from_str::<Vec<String>>).transpose()?;
+    Ok(DraftPostSettings { slug, license, lang, labels, content_warning, visibility })
+}
+
+/// Replaces the post settings of a draft. They should be validated with `validate_post_settings()`.
+pub fn set_draft_post_settings(conn: &rusqlite::Connection, draft_id: u32, settings: &DraftPostSettings) -> anyhow::Result<()> {
+    let post_labels = settings.labels.as_ref().map(serde_json::to_string).transpose()?;
+    conn.execute(
+        "UPDATE draft SET slug = ?, license = ?, language_code = ?, labels = ?, content_warning = ?, visibility = ? WHERE id = ?",
+        (&settings.slug, &settings.license, &settings.lang, &post_labels, &settings.content_warning, &settings.visibility, draft_id),
+    )?;
+    Ok(())
+}
+
+/// Applies the post settings of a draft to the post, as `post/update` does.
+fn apply_post_settings(conn: &rusqlite::Connection, post_id: u32, channel_id: u32, settings: &DraftPostSettings) -> anyhow::Result<()> {
+    if let Some(slug) = &settings.slug {
+        let slug = if slug.is_empty() { None } else { Some(slug.as_str()) };
+        slugs::set_post_slug(conn, post_id, channel_id, slug)?;
+    }
+    if let Some(license) = &settings.license {
+        let license = if license.is_empty() { None } else { Some(license.as_str()) };
+        conn.execute(
+            "UPDATE post SET license = ? WHERE id = ?",
+            (license, post_id),
+        )?;
+    }
+    if let Some(lang) = &settings.lang {
+        let lang = if lang.is_empty() { None } else { Some(lang.as_str()) };
+        conn.execute(
+            "UPDATE post SET language_code = ? WHERE id = ?",
+            (lang, post_id),
+        )?;
+    }
+    if let Some(post_labels) = &settings.labels {
+        labels::set_post_labels(conn, post_id, post_labels, false)?;
+    }
+    if let Some(content_warning) = &settings.content_warning {
+        let content_warning = if content_warning.is_empty() { None } else { Some(content_warning.as_str()) };
+        conn.execute(
+            "UPDATE post SET content_warning = ? WHERE id = ?",
+            (content_warning, post_id),
+        )?;
+    }
+    if let Some(visibility) = &settings.visibility {
+        let visibility = visibility.parse::<PostVisibility>()?;
+        conn.execute(
+            "UPDATE post SET visibility = ? WHERE id = ?",
+            (visibility.as_str(), post_id),
+        )?;
+    }
+    Ok(())
+}
+
+pub fn get_draft_info(conn: &rusqlite::Connection, draft_id: u32) -> anyhow::Result<DraftInfo> {
+    let tags = get_draft_tags(conn, draft_id)?;
+    let post_settings = get_draft_post_settings(conn, draft_id)?;
+
+    let draft = conn.query_row(
+        &format!(
+            "
+                SELECT draft.uuid, channel.uuid, channel.handle, channel.name, channel.language_code, post.uuid, author.uuid, author.name, draft.updated_date, draft.publish_date, draft.title, draft.draft_text,
+                {author_icon},
+                {channel_icon}
+                FROM draft
+                INNER JOIN channel ON draft.channel_id = channel.id
+                INNER JOIN author ON draft.author_id = author.id
+                LEFT JOIN post ON draft.post_id = post.id
+                WHERE draft.id = ?
+            ",
+            author_icon = icons::author_summary_icon_query("author.id"), channel_icon = icons::channel_summary_icon_query("channel.id"),
+        ),
+        [draft_id],
+        |row| {
+            let uuid: String = row.get(0)?;
+            let channel_uuid: String = row.get(1)?;
+            let channel_handle: String = row.get(2)?;
+            let channel_name: String = row.get(3)?;
+            let channel_lang: String = row.get(4)?;
+            let post_uuid: Option<String> = row.get(5)?;
+            let author_uuid: String = row.get(6)?;
+            let author_name: String = row.get(7)?;
+            let updated_date: u64 = row.get(8)?;
+            let publish_at: Option<u64> = row.get(9)?;
+            let title: String = row.get(10)?;
+            let text: String = row.get(11)?;
+            let author_icon_sha256: Option<String> = row.get(12)?;
+            let channel_icon_sha256: Option<String> = row.get(13)?;
+
+            let channel = ChannelSummary::new(&channel_uuid, &channel_handle, &channel_name, &channel_lang, channel_icon_sha256.as_deref());
+            let author = AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref());
+            Ok(DraftInfo::new(&uuid, &channel, post_uuid.as_deref(), &author, updated_date, publish_at, &title, &text, tags, post_settings))
+        }
+    )?;
+    Ok(draft)
+}
+
+/// Turns a draft into a revision and deletes the draft, applying its post settings.
+/// A new post is created for drafts of new posts.
+///
+/// Fails if the draft's author is no longer an author of the channel
+/// (or may not edit the post), if the channel or the post has been deleted,
+/// or if the draft's slug is now taken by another post.
+pub fn publish_draft(conn: &rusqlite::Connection, draft_id: u32, published_date: u64) -> anyhow::Result<PostInfo> {
+    let (channel_id, post_id, author_id, title, text, channel_uuid, channel_handle, channel_name, channel_lang, author_uuid, author_name, author_icon_sha256, channel_icon_sha256) = conn.query_row(
+        &format!(
+            "
+                SELECT draft.channel_id, draft.post_id, draft.author_id, draft.title, draft.draft_text, channel.uuid, channel.handle, channel.name, channel.language_code, author.uuid, author.name,
+                {author_icon},
+                {channel_icon}
+                FROM draft, channel, author, channel_author
+                WHERE draft.id = ? AND draft.channel_id = channel.id AND channel.is_deleted = 0 AND draft.author_id = author.id AND author.is_deleted = 0
+                AND channel_author.channel_id = channel.id AND channel_author.author_id = author.id
+            ",
+            author_icon = icons::author_summary_icon_query("author.id"), channel_icon = icons::channel_summary_icon_query("channel.id"),
+        ),
+        [draft_id],
+        |row| {
+            let channel_id: u32 = row.get(0)?;
+            let post_id: Option<u32> = row.get(1)?;
+            let author_id: u32 = row.get(2)?;
+            let title: String = row.get(3)?;
+            let text: String = row.get(4)?;
+            let channel_uuid: String = row.get(5)?;
+            let channel_handle: String = row.get(6)?;
+            let channel_name: String = row.get(7)?;
+            let channel_lang: String = row.get(8)?;
+            let author_uuid: String = row.get(9)?;
+            let author_name: String = row.get(10)?;
+            let author_icon_sha256: Option<String> = row.get(11)?;
+            let channel_icon_sha256: Option<String> = row.get(12)?;
+            Ok((channel_id, post_id, author_id, title, text, channel_uuid, channel_handle, channel_name, channel_lang, author_uuid, author_name, author_icon_sha256, channel_icon_sha256))
+        }
+    ).map_err(|_| anyhow::anyhow!("Draft cannot be published"))?;
+
+    let tags = get_draft_tags(conn, draft_id)?;
+    let post_settings = get_draft_post_settings(conn, draft_id)?;
+
+    let (post_id, post_uuid) = if let Some(post_id) = post_id {
+        let post_uuid = conn.query_row(
+            "SELECT uuid FROM post WHERE id = ? AND is_deleted = 0",
+            [post_id],
+            |row| row.get::<_, String>(0),
+        ).map_err(|_| anyhow::anyhow!("Post not found"))?;
+        roles::check_post_editable(conn, channel_id, post_id, author_id)?;
+
+        conn.execute("DELETE FROM post_tag WHERE post_id = ?", [post_id])?;
+        conn.execute(
+            "UPDATE post SET updated_date = ? WHERE id = ?",
+            (&published_date, &post_id),
+        )?;
+        (post_id, post_uuid)
+    } else {
+        let post_uuid = uuid::Uuid::new_v4().to_string();
+        conn.execute(
+            "INSERT INTO post (uuid, channel_id, published_date, updated_date) VALUES (?, ?, ?, ?)",
+            (&post_uuid, &channel_id, &published_date, &published_date),
+        )?;
+
+        let post_id = conn.query_row(
+            "SELECT id FROM post WHERE uuid = ?",
+            [&post_uuid],
+            |row| row.get::<_, u32>(0),
+        )?;
+        (post_id, post_uuid)
+    };
+
+    for tag in &tags {
+        conn.execute(
+            "INSERT INTO post_tag (post_id, name) VALUES (?, ?)",
+            (&post_id, tag),
+        )?;
+    }
+
+    apply_post_settings(conn, post_id, channel_id, &post_settings)?;
+
+    let slug = slugs::get_post_slug(conn, post_id)?;
+    let post_labels = labels::get_post_labels(conn, post_id)?;
+    let (license, lang, content_warning, visibility, post_published_date) = conn.query_row(
+        "SELECT COALESCE(post.license, channel.license), COALESCE(post.language_code, channel.language_code), post.content_warning, post.visibility, post.published_date FROM post, channel WHERE post.id = ? AND post.channel_id = channel.id",
+        [post_id],
+        |row| {
+            let license: String = row.get(0)?;
+            let lang: String = row.get(1)?;
+            let content_warning: Option<String> = row.get(2)?;
+            let visibility: String = row.get(3)?;
+            let post_published_date: u64 = row.get(4)?;
+            Ok((license, lang, content_warning, visibility, post_published_date))
+        },
+    )?;
+
+    let revision_uuid = uuid::Uuid::new_v4().to_string();
+    conn.execute(
+        "INSERT INTO revision (uuid, post_id, author_id, created_date, title, revision_text) VALUES (?, ?, ?, ?, ?, ?)",
+        (&revision_uuid, &post_id, &author_id, &published_date, &title, &text),
+    )?;
+
+    media::record_post_media(conn, post_id, &text)?;
+
+    conn.execute("DELETE FROM draft WHERE id = ?", [draft_id])?;
+
+    let author = AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref());
+    let revision = RevisionInfo::new(&revision_uuid, &author, published_date, &title, &text);
+    let channel = ChannelSummary::new(&channel_uuid, &channel_handle, &channel_name, &channel_lang, channel_icon_sha256.as_deref());
+    let mut post = PostInfo::new(&post_uuid, slug.as_deref(), &channel, tags, &revision, &author);
+    post.set_license(&license);
+    post.set_lang(&lang);
+    post.set_labels(post_labels, content_warning.as_deref());
+    post.set_visibility(visibility.parse()?);
+    post.set_published_date(post_published_date);
+    Ok(post)
+}
+
+/// Publishes all drafts whose scheduled time has passed.
+/// Drafts that cannot be published are unscheduled so that they stay visible to their authors.
+/// Returns the number of published drafts.
+pub fn publish_due_drafts(state: &AppState) -> anyhow::Result<usize> {
+    let now = sys_time::get_sys_time_in_secs();
+    let mut db_connection = state.db_connection.lock().unwrap();
+
+    let draft_ids = {
+        let trx = db_connection.transaction()?;
+        let mut stmt = trx.prepare("SELECT id FROM draft WHERE publish_date IS NOT NULL AND publish_date <= ? ORDER BY publish_date")?;
+        let mut rows = stmt.query([now])?;
+
+        let mut draft_ids = Vec::new();
+        while let Some(row) = rows.next()? {
+            let draft_id: u32 = row.get(0)?;
+            draft_ids.push(draft_id);
+        }
+        draft_ids
+    };
+
+    let mut published_count = 0;
+    for draft_id in draft_ids {
+        let trx = db_connection.transaction()?;
+        match publish_draft(&trx, draft_id, now) {
+            Ok(_) => {
+                trx.commit()?;
+                published_count += 1;
+            },
+            Err(e) => {
+                trx.rollback()?;
+                log::warn!("Failed to publish draft {}: {}", draft_id, e);
+
+                let trx = db_connection.transaction()?;
+                trx.execute("UPDATE draft SET publish_date = NULL WHERE id = ?", [draft_id])?;
+                trx.commit()?;
+            },
+        }
+    }
+
+    Ok(published_count)
+}
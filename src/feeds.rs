@@ -0,0 +1,555 @@
+
+use std::collections::HashMap;
+
+use axum::{
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use rusqlite::OptionalExtension;
+use sha2::{Sha256, Digest};
+
+use crate::frontend::Frontend;
+use crate::labels;
+use crate::listings;
+use crate::limits;
+use crate::markdown;
+use crate::state::AppState;
+use crate::sys_time;
+use crate::icons;
+
+use crate::api::v1::types::{
+    AuthorInfo,
+    AuthorSummary,
+    ChannelInfo,
+    ChannelSummary,
+    PostInfo,
+    RevisionInfo,
+};
+
+
+/// Feeds are cheap to build but polled often; readers may get them a few minutes late.
+pub static FEED_CACHE_CONTROL: &str = "public, max-age=300";
+
+static FEED_GENERATOR_NAME: &str = "alarkhabil-server";
+static FEED_GENERATOR_URI: &str = "https://github.com/metastable-void/alarkhabil-server";
+
+/// Posts a feed is built from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedSource {
+    Instance,
+    ChannelByUuid(String),
+    ChannelByHandle(String),
+    Author(String),
+    Tag(String),
+}
+
+impl FeedSource {
+    /// The query parameter selecting the feed, as parsed by `from_query()`.
+    pub fn query_param(&self) -> Option<(&'static str, &str)> {
+        match self {
+            FeedSource::Instance => None,
+            FeedSource::ChannelByUuid(channel_uuid) => Some(("channel_uuid", channel_uuid)),
+            FeedSource::ChannelByHandle(channel_handle) => Some(("channel_handle", channel_handle)),
+            FeedSource::Author(author_uuid) => Some(("author_uuid", author_uuid)),
+            FeedSource::Tag(tag_name) => Some(("tag_name", tag_name)),
+        }
+    }
+
+    /// Parses the `channel_uuid`, `channel_handle`, `author_uuid` and `tag_name` query parameters.
+    /// Without any of them, the feed covers the whole instance.
+    pub fn from_query(params: &HashMap<String, String>) -> anyhow::Result<FeedSource> {
+        let mut sources: Vec<FeedSource> = [
+            params.get("channel_uuid").map(|uuid| FeedSource::ChannelByUuid(uuid.to_owned())),
+            params.get("channel_handle").map(|handle| FeedSource::ChannelByHandle(handle.to_owned())),
+            params.get("author_uuid").map(|uuid| FeedSource::Author(uuid.to_owned())),
+            params.get("tag_name").map(|tag_name| FeedSource::Tag(tag_name.to_owned())),
+        ].into_iter().flatten().collect();
+
+        match sources.len() {
+            0 => Ok(FeedSource::Instance),
+            1 => Ok(sources.remove(0)),
+            _ => Err(anyhow::anyhow!("Only one of channel_uuid, channel_handle, author_uuid and tag_name parameters is allowed")),
+        }
+    }
+}
+
+/// Whether feed entries carry the full text of posts or only plain-text excerpts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedContent {
+    Full,
+    Excerpt,
+}
+
+impl FeedContent {
+    pub fn from_query(content: Option<&String>) -> anyhow::Result<FeedContent> {
+        match content.map(|content| content.as_str()) {
+            None | Some("full") => Ok(FeedContent::Full),
+            Some("excerpt") => Ok(FeedContent::Excerpt),
+            Some(_) => Err(anyhow::anyhow!("Invalid content")),
+        }
+    }
+}
+
+/// What a feed is about, as found in the database.
+#[derive(Debug, Clone)]
+pub enum FeedOwner {
+    Instance,
+    Channel(ChannelInfo),
+    Author(AuthorInfo),
+    Tag(String),
+}
+
+/// A post in a feed, as of its latest revision.
+#[derive(Debug, Clone)]
+pub struct FeedEntry {
+    pub post: PostInfo,
+    pub url: String,
+    pub author_url: String,
+
+    /// Plain-text excerpt of the post.
+    pub summary: String,
+
+    /// Sanitized HTML of the full text, `None` for excerpt feeds.
+    pub content_html: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Feed {
+    pub owner: FeedOwner,
+
+    /// Stable identifier of the feed (a URL or a URN).
+    pub id: String,
+    pub title: String,
+    pub description: String,
+
+    /// Frontend page corresponding to the feed.
+    pub url: String,
+
+    /// Query parameters selecting the feed, for links to the feed itself.
+    pub query: Vec<(&'static str, String)>,
+
+    pub entries: Vec<FeedEntry>,
+}
+
+impl Feed {
+    /// Date of the latest update of any entry.
+    /// Empty feeds use a date that stays the same between requests, so that they validate with `Last-Modified`:
+    /// the creation date of the channel, the registration date of the author, or the UNIX epoch for other feeds.
+    pub fn updated_date(&self) -> u64 {
+        let owner_date = match &self.owner {
+            FeedOwner::Channel(channel) => channel.created_date(),
+            FeedOwner::Author(author) => author.created_date(),
+            FeedOwner::Instance | FeedOwner::Tag(_) => 0,
+        };
+        self.entries.iter().map(|entry| entry.post.updated_date()).max().unwrap_or(owner_date)
+    }
+
+    /// Language of the channel for channel feeds. Other feeds mix languages.
+    pub fn lang(&self) -> Option<&str> {
+        match &self.owner {
+            FeedOwner::Channel(channel) if !channel.lang().is_empty() => Some(channel.lang()),
+            _ => None,
+        }
+    }
+
+    /// License of the channel for channel feeds. Entries carry their own licenses.
+    pub fn license(&self) -> Option<&str> {
+        match &self.owner {
+            FeedOwner::Channel(channel) => Some(channel.license()),
+            _ => None,
+        }
+    }
+}
+
+fn describe(description_text: &str, fallback: String) -> String {
+    let description = markdown::render_excerpt(description_text, limits::FEED_EXCERPT_LENGTH);
+    if description.is_empty() {
+        fallback
+    } else {
+        description
+    }
+}
+
+/// Loads the latest posts of a feed with the same conditions as `/api/v1/channel/posts` and `/api/v1/tag/posts`:
+/// public posts ordered by their last update.
+/// Returns `None` if the channel or the author is not found.
+pub fn load_feed(state: &AppState, source: &FeedSource, content: FeedContent) -> anyhow::Result<Option<Feed>> {
+    let frontend = &state.frontend;
+    let mut db_connection = state.db_connection.lock().unwrap();
+    let trx = db_connection.transaction()?;
+
+    let (mut feed, channel_id, author_id, tag_name) = match source {
+        FeedSource::Instance => {
+            let feed = Feed {
+                owner: FeedOwner::Instance,
+                id: frontend.base_url().to_string(),
+                title: frontend.site_name().to_string(),
+                description: format!("Posts on {}", frontend.site_name()),
+                url: frontend.base_url().to_string(),
+                query: Vec::new(),
+                entries: Vec::new(),
+            };
+            (feed, None, None, None)
+        },
+        FeedSource::ChannelByUuid(_) | FeedSource::ChannelByHandle(_) => {
+            let (column, value) = match source {
+                FeedSource::ChannelByUuid(channel_uuid) => ("uuid", channel_uuid),
+                FeedSource::ChannelByHandle(channel_handle) => ("handle", channel_handle),
+                _ => unreachable!(),
+            };
+            let channel = trx.query_row(
+                &format!("SELECT id, uuid, handle, name, created_date, language_code, description_text, license FROM channel WHERE is_deleted = 0 AND {} = ?", column),
+                [value],
+                |row| {
+                    let channel_id: u32 = row.get(0)?;
+                    let channel_uuid: String = row.get(1)?;
+                    let handle: String = row.get(2)?;
+                    let name: String = row.get(3)?;
+                    let created_date: u64 = row.get(4)?;
+                    let language_code: String = row.get(5)?;
+                    let description_text: String = row.get(6)?;
+                    let license: String = row.get(7)?;
+
+                    let mut channel = ChannelInfo::new(&channel_uuid, &handle, &name, created_date, &language_code, &description_text);
+                    channel.set_license(&license);
+                    Ok((channel_id, channel))
+                },
+            ).optional()?;
+            let (channel_id, channel) = if let Some(channel) = channel {
+                channel
+            } else {
+                return Ok(None);
+            };
+
+            let feed = Feed {
+                id: format!("urn:uuid:{}", channel.uuid()),
+                title: channel.name().to_string(),
+                description: describe(channel.description_text(), format!("Posts in {}", channel.name())),
+                url: frontend.channel_url(channel.handle()),
+                owner: FeedOwner::Channel(channel),
+                query: Vec::new(),
+                entries: Vec::new(),
+            };
+            (feed, Some(channel_id), None, None)
+        },
+        FeedSource::Author(author_uuid) => {
+            let author = trx.query_row(
+                "SELECT id, name, registered_date, description_text FROM author WHERE is_deleted = 0 AND uuid = ?",
+                [author_uuid],
+                |row| {
+                    let author_id: u32 = row.get(0)?;
+                    let name: String = row.get(1)?;
+                    let created_date: u64 = row.get(2)?;
+                    let description_text: String = row.get(3)?;
+                    Ok((author_id, AuthorInfo::new(author_uuid, &name, created_date, &description_text)))
+                },
+            ).optional()?;
+            let (author_id, author) = if let Some(author) = author {
+                author
+            } else {
+                return Ok(None);
+            };
+
+            let feed = Feed {
+                id: format!("urn:uuid:{}", author.uuid()),
+                title: author.name().to_string(),
+                description: describe(author.description_text(), format!("Posts by {}", author.name())),
+                url: frontend.author_url(author.uuid()),
+                owner: FeedOwner::Author(author),
+                query: Vec::new(),
+                entries: Vec::new(),
+            };
+            (feed, None, Some(author_id), None)
+        },
+        FeedSource::Tag(tag_name) => {
+            let feed = Feed {
+                owner: FeedOwner::Tag(tag_name.to_owned()),
+                id: frontend.tag_url(tag_name),
+                title: format!("{}: {}", frontend.site_name(), tag_name),
+                description: format!("Posts tagged {} on {}", tag_name, frontend.site_name()),
+                url: frontend.tag_url(tag_name),
+                query: Vec::new(),
+                entries: Vec::new(),
+            };
+            (feed, None, None, Some(tag_name))
+        },
+    };
+
+    if let Some((name, value)) = source.query_param() {
+        feed.query.push((name, value.to_string()));
+    }
+    if content == FeedContent::Excerpt {
+        feed.query.push(("content", "excerpt".to_string()));
+    }
+
+    let mut stmt = trx.prepare(
+        &format!(
+            "
+                SELECT post.id, post.uuid, revision.uuid, MAX(revision.created_date), revision.title, revision.revision_text, author.uuid, author.name,
+                {author_icon},
+                channel.uuid, channel.handle, channel.name, channel.language_code, post_slug.slug,
+                COALESCE(post.language_code, channel.language_code), COALESCE(post.license, channel.license), post.published_date, post.updated_date,
+                {post_labels}, post.content_warning
+                FROM {post_tables}
+                WHERE {post_conditions}
+                AND (?1 IS NULL OR channel.id = ?1)
+                AND (?2 IS NULL OR EXISTS (SELECT 1 FROM revision AS author_revision WHERE author_revision.post_id = post.id AND author_revision.is_deleted = 0 AND author_revision.author_id = ?2))
+                AND (?3 IS NULL OR EXISTS (SELECT 1 FROM post_tag WHERE post_tag.post_id = post.id AND post_tag.name = ?3))
+                GROUP BY post.id
+                ORDER BY post.updated_date DESC, post.id DESC
+                LIMIT ?4
+            ",
+            author_icon = icons::author_summary_icon_query("author.id"),
+            post_tables = listings::POST_LISTING_TABLES,
+            post_conditions = listings::POST_LISTING_CONDITIONS,
+            post_labels = labels::POST_LABELS_COLUMN,
+        )
+    )?;
+    let mut tag_stmt = trx.prepare("SELECT DISTINCT name FROM post_tag WHERE post_id = ? ORDER BY name ASC")?;
+
+    let mut rows = stmt.query((channel_id, author_id, tag_name, limits::MAX_FEED_ENTRIES))?;
+    while let Some(row) = rows.next()? {
+        let post_id: u32 = row.get(0)?;
+        let post_uuid: String = row.get(1)?;
+        let revision_uuid: String = row.get(2)?;
+        let revision_date: u64 = row.get(3)?;
+        let title: String = row.get(4)?;
+        let revision_text: String = row.get(5)?;
+        let author_uuid: String = row.get(6)?;
+        let author_name: String = row.get(7)?;
+        let author_icon_sha256: Option<String> = row.get(8)?;
+        let channel_uuid: String = row.get(9)?;
+        let channel_handle: String = row.get(10)?;
+        let channel_name: String = row.get(11)?;
+        let channel_lang: String = row.get(12)?;
+        let slug: Option<String> = row.get(13)?;
+        let lang: String = row.get(14)?;
+        let license: String = row.get(15)?;
+        let published_date: u64 = row.get(16)?;
+        let updated_date: u64 = row.get(17)?;
+        let post_labels: Option<String> = row.get(18)?;
+        let content_warning: Option<String> = row.get(19)?;
+
+        let tags = tag_stmt.query_map([post_id], |row| row.get(0))?.collect::<rusqlite::Result<Vec<String>>>()?;
+
+        let channel = ChannelSummary::new(&channel_uuid, &channel_handle, &channel_name, &channel_lang, None);
+        let author = AuthorSummary::new(&author_uuid, &author_name, author_icon_sha256.as_deref());
+        let revision = RevisionInfo::new(&revision_uuid, &author, revision_date, &title, &revision_text);
+        let mut post = PostInfo::new(&post_uuid, slug.as_deref(), &channel, tags, &revision, &author);
+        post.set_published_date(published_date);
+        post.set_updated_date(updated_date);
+        post.set_license(&license);
+        post.set_lang(&lang);
+        post.set_labels(labels::split_labels(post_labels), content_warning.as_deref());
+
+        let content_html = match content {
+            FeedContent::Full => Some(state.markdown_cache.lock().unwrap().render(&revision_uuid, &revision_text)),
+            FeedContent::Excerpt => None,
+        };
+
+        feed.entries.push(FeedEntry {
+            url: frontend.post_url(&channel_handle, &post_uuid, slug.as_deref()),
+            author_url: frontend.author_url(&author_uuid),
+            summary: markdown::render_excerpt(&revision_text, limits::FEED_EXCERPT_LENGTH),
+            content_html,
+            post,
+        });
+    }
+
+    Ok(Some(feed))
+}
+
+/// Builds the response for a rendered feed.
+/// Feeds are validated with their ETag (a hash of the document), and with `Last-Modified` by clients that do not keep ETags.
+/// As in RFC 9110, `If-Modified-Since` is ignored if `If-None-Match` is present.
+pub fn feed_response(request_headers: &HeaderMap, content_type: &'static str, last_modified: u64, document: String) -> Response {
+    let etag = format!("\"{}\"", hex::encode(&Sha256::digest(document.as_bytes())[..16]));
+    let headers = [
+        (header::CONTENT_TYPE, content_type.to_string()),
+        (header::CACHE_CONTROL, FEED_CACHE_CONTROL.to_string()),
+        (header::ETAG, etag.clone()),
+        (header::LAST_MODIFIED, sys_time::format_http_date(last_modified)),
+    ];
+
+    let not_modified = if request_headers.contains_key(header::IF_NONE_MATCH) {
+        request_headers.get_all(header::IF_NONE_MATCH).iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|value| value.trim().trim_start_matches("W/") == etag || value.trim() == "*")
+    } else {
+        request_headers.get(header::IF_MODIFIED_SINCE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(sys_time::parse_http_date)
+            .is_some_and(|if_modified_since| if_modified_since >= last_modified)
+    };
+    if not_modified {
+        return (StatusCode::NOT_MODIFIED, headers).into_response();
+    }
+
+    (headers, document).into_response()
+}
+
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // control characters are not allowed in XML 1.0
+            c if c.is_control() && c != '\n' && c != '\r' && c != '\t' => {},
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn push_element(xml: &mut String, indent: usize, name: &str, text: &str) {
+    xml.push_str(&format!("{}<{}>{}</{}>\n", "  ".repeat(indent), name, escape_xml(text), name));
+}
+
+/// Renders a feed as an Atom (RFC 4287) document.
+pub fn render_atom(feed: &Feed) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    match feed.lang() {
+        Some(lang) => xml.push_str(&format!("<feed xmlns=\"http://www.w3.org/2005/Atom\" xml:lang=\"{}\">\n", escape_xml(lang))),
+        None => xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n"),
+    }
+    push_element(&mut xml, 1, "id", &feed.id);
+    push_element(&mut xml, 1, "title", &feed.title);
+    push_element(&mut xml, 1, "subtitle", &feed.description);
+    xml.push_str(&format!("  <link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>\n", escape_xml(&feed.url)));
+    push_element(&mut xml, 1, "updated", &sys_time::format_rfc3339(feed.updated_date()));
+    if let Some(license) = feed.license() {
+        push_element(&mut xml, 1, "rights", license);
+    }
+    xml.push_str(&format!("  <generator uri=\"{}\">{}</generator>\n", FEED_GENERATOR_URI, FEED_GENERATOR_NAME));
+
+    for entry in &feed.entries {
+        let post = &entry.post;
+        if post.lang().is_empty() {
+            xml.push_str("  <entry>\n");
+        } else {
+            xml.push_str(&format!("  <entry xml:lang=\"{}\">\n", escape_xml(post.lang())));
+        }
+        push_element(&mut xml, 2, "id", &format!("urn:uuid:{}", post.post_uuid()));
+        push_element(&mut xml, 2, "title", post.title());
+        xml.push_str(&format!("    <link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>\n", escape_xml(&entry.url)));
+        push_element(&mut xml, 2, "published", &sys_time::format_rfc3339(post.published_date()));
+        push_element(&mut xml, 2, "updated", &sys_time::format_rfc3339(post.updated_date()));
+        xml.push_str("    <author>\n");
+        push_element(&mut xml, 3, "name", post.author().name());
+        push_element(&mut xml, 3, "uri", &entry.author_url);
+        xml.push_str("    </author>\n");
+        for tag in post.tags() {
+            xml.push_str(&format!("    <category term=\"{}\"/>\n", escape_xml(tag)));
+        }
+        push_element(&mut xml, 2, "rights", post.license());
+        push_element(&mut xml, 2, "summary", &entry.summary);
+        if let Some(content_html) = &entry.content_html {
+            xml.push_str(&format!("    <content type=\"html\">{}</content>\n", escape_xml(content_html)));
+        }
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/// Renders a feed as an RSS 2.0 document.
+/// Entries carry the full text (as HTML) or the excerpt in `description`.
+pub fn render_rss(feed: &Feed) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<rss version=\"2.0\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n");
+    xml.push_str("  <channel>\n");
+    push_element(&mut xml, 2, "title", &feed.title);
+    push_element(&mut xml, 2, "link", &feed.url);
+    push_element(&mut xml, 2, "description", &feed.description);
+    if let Some(lang) = feed.lang() {
+        push_element(&mut xml, 2, "language", lang);
+    }
+    if let Some(license) = feed.license() {
+        push_element(&mut xml, 2, "copyright", license);
+    }
+    push_element(&mut xml, 2, "lastBuildDate", &sys_time::format_rfc2822(feed.updated_date()));
+    push_element(&mut xml, 2, "generator", FEED_GENERATOR_NAME);
+
+    for entry in &feed.entries {
+        let post = &entry.post;
+        xml.push_str("    <item>\n");
+        push_element(&mut xml, 3, "title", post.title());
+        push_element(&mut xml, 3, "link", &entry.url);
+        xml.push_str(&format!("      <guid isPermaLink=\"false\">urn:uuid:{}</guid>\n", escape_xml(post.post_uuid())));
+        push_element(&mut xml, 3, "pubDate", &sys_time::format_rfc2822(post.published_date()));
+        push_element(&mut xml, 3, "dc:creator", post.author().name());
+        push_element(&mut xml, 3, "dc:rights", post.license());
+        for tag in post.tags() {
+            push_element(&mut xml, 3, "category", tag);
+        }
+        push_element(&mut xml, 3, "description", entry.content_html.as_deref().unwrap_or(&entry.summary));
+        xml.push_str("    </item>\n");
+    }
+
+    xml.push_str("  </channel>\n");
+    xml.push_str("</rss>\n");
+    xml
+}
+
+/// Renders a feed as a JSON Feed 1.1 document.
+/// Excerpt feeds carry the excerpt as `content_text`, since items need some content.
+/// Licenses of items are given in the `_alarkhabil` extension object.
+pub fn render_json_feed(feed: &Feed, frontend: &Frontend) -> serde_json::Value {
+    let items: Vec<serde_json::Value> = feed.entries.iter().map(|entry| {
+        let post = &entry.post;
+        let mut item = serde_json::json!({
+            "id": format!("urn:uuid:{}", post.post_uuid()),
+            "url": entry.url,
+            "title": post.title(),
+            "summary": entry.summary,
+            "date_published": sys_time::format_rfc3339(post.published_date()),
+            "date_modified": sys_time::format_rfc3339(post.updated_date()),
+            "authors": [
+                {
+                    "name": post.author().name(),
+                    "url": entry.author_url,
+                },
+            ],
+            "tags": post.tags(),
+            "_alarkhabil": {
+                "license": post.license(),
+            },
+        });
+        match &entry.content_html {
+            Some(content_html) => item["content_html"] = serde_json::json!(content_html),
+            None => item["content_text"] = serde_json::json!(entry.summary),
+        }
+        if !post.lang().is_empty() {
+            item["language"] = serde_json::json!(post.lang());
+        }
+        item
+    }).collect();
+
+    let query: Vec<(&str, &str)> = feed.query.iter().map(|(name, value)| (*name, value.as_str())).collect();
+    let mut json_feed = serde_json::json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": feed.title,
+        "home_page_url": feed.url,
+        "feed_url": frontend.feed_url("json", &query),
+        "description": feed.description,
+        "items": items,
+    });
+    if let Some(lang) = feed.lang() {
+        json_feed["language"] = serde_json::json!(lang);
+    }
+    if let FeedOwner::Author(author) = &feed.owner {
+        json_feed["authors"] = serde_json::json!([
+            {
+                "name": author.name(),
+                "url": feed.url,
+            },
+        ]);
+    }
+    json_feed
+}
@@ -0,0 +1,157 @@
+
+use std::env;
+
+
+static DEFAULT_CHANNEL_URL_TEMPLATE: &str = "{base_url}/channel/{channel_handle}";
+static DEFAULT_POST_URL_TEMPLATE: &str = "{base_url}/channel/{channel_handle}/{post}";
+static DEFAULT_AUTHOR_URL_TEMPLATE: &str = "{base_url}/author/{author_uuid}";
+static DEFAULT_TAG_URL_TEMPLATE: &str = "{base_url}/tag/{tag_name}";
+static DEFAULT_META_PAGE_URL_TEMPLATE: &str = "{base_url}/meta/{page_name}";
+static DEFAULT_SITEMAP_URL_TEMPLATE: &str = "{base_url}/sitemap.xml?page={page}";
+static DEFAULT_FEED_URL_TEMPLATE: &str = "{base_url}/feed/{format}";
+
+/// Frontend serving the pages of this instance.
+/// Feeds and other documents meant for readers link to its pages rather than to the API.
+///
+/// Page URLs are built from templates. `{base_url}` is replaced with the base URL,
+/// and the other placeholders with percent-encoded values.
+#[derive(Debug, Clone)]
+pub struct Frontend {
+    base_url: String,
+    site_name: String,
+    channel_url_template: String,
+    post_url_template: String,
+    author_url_template: String,
+    tag_url_template: String,
+    meta_page_url_template: String,
+    sitemap_url_template: String,
+    feed_url_template: String,
+}
+
+impl Frontend {
+    pub fn new(base_url: &str, site_name: &str) -> Frontend {
+        Frontend {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            site_name: site_name.to_string(),
+            channel_url_template: DEFAULT_CHANNEL_URL_TEMPLATE.to_string(),
+            post_url_template: DEFAULT_POST_URL_TEMPLATE.to_string(),
+            author_url_template: DEFAULT_AUTHOR_URL_TEMPLATE.to_string(),
+            tag_url_template: DEFAULT_TAG_URL_TEMPLATE.to_string(),
+            meta_page_url_template: DEFAULT_META_PAGE_URL_TEMPLATE.to_string(),
+            sitemap_url_template: DEFAULT_SITEMAP_URL_TEMPLATE.to_string(),
+            feed_url_template: DEFAULT_FEED_URL_TEMPLATE.to_string(),
+        }
+    }
+
+    pub fn new_from_env() -> Frontend {
+        let base_url = env::var("FRONTEND_BASE_URL").unwrap_or("".to_string());
+        let base_url = if base_url.is_empty() {
+            log::warn!("FRONTEND_BASE_URL not set, using http://localhost");
+            "http://localhost".to_string()
+        } else {
+            log::info!("Using frontend at {}", base_url);
+            base_url
+        };
+
+        let site_name = env::var("SITE_NAME").unwrap_or("".to_string());
+        let site_name = if site_name.is_empty() {
+            "Alarkhabil".to_string()
+        } else {
+            site_name
+        };
+
+        let mut frontend = Frontend::new(&base_url, &site_name);
+        let templates = [
+            ("FRONTEND_CHANNEL_URL_TEMPLATE", &mut frontend.channel_url_template),
+            ("FRONTEND_POST_URL_TEMPLATE", &mut frontend.post_url_template),
+            ("FRONTEND_AUTHOR_URL_TEMPLATE", &mut frontend.author_url_template),
+            ("FRONTEND_TAG_URL_TEMPLATE", &mut frontend.tag_url_template),
+            ("FRONTEND_META_PAGE_URL_TEMPLATE", &mut frontend.meta_page_url_template),
+            ("FRONTEND_SITEMAP_URL_TEMPLATE", &mut frontend.sitemap_url_template),
+            ("FRONTEND_FEED_URL_TEMPLATE", &mut frontend.feed_url_template),
+        ];
+        for (name, template) in templates {
+            let value = env::var(name).unwrap_or("".to_string());
+            if !value.is_empty() {
+                log::info!("Using {}={}", name, value);
+                *template = value;
+            }
+        }
+        frontend
+    }
+
+    /// Base URL without a trailing slash.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn site_name(&self) -> &str {
+        &self.site_name
+    }
+
+    fn fill_template(&self, template: &str, values: &[(&str, &str)]) -> String {
+        let mut url = template.replace("{base_url}", &self.base_url);
+        for (name, value) in values {
+            url = url.replace(&format!("{{{}}}", name), &encode_path_segment(value));
+        }
+        url
+    }
+
+    /// Placeholders: `{channel_handle}`.
+    pub fn channel_url(&self, channel_handle: &str) -> String {
+        self.fill_template(&self.channel_url_template, &[("channel_handle", channel_handle)])
+    }
+
+    /// Placeholders: `{channel_handle}`, `{post_uuid}` and `{post}` (the slug if the post has one, the uuid otherwise).
+    pub fn post_url(&self, channel_handle: &str, post_uuid: &str, slug: Option<&str>) -> String {
+        self.fill_template(&self.post_url_template, &[
+            ("channel_handle", channel_handle),
+            ("post_uuid", post_uuid),
+            ("post", slug.unwrap_or(post_uuid)),
+        ])
+    }
+
+    /// Placeholders: `{author_uuid}`.
+    pub fn author_url(&self, author_uuid: &str) -> String {
+        self.fill_template(&self.author_url_template, &[("author_uuid", author_uuid)])
+    }
+
+    /// Placeholders: `{tag_name}`.
+    pub fn tag_url(&self, tag_name: &str) -> String {
+        self.fill_template(&self.tag_url_template, &[("tag_name", tag_name)])
+    }
+
+    /// Placeholders: `{page_name}`.
+    pub fn meta_page_url(&self, page_name: &str) -> String {
+        self.fill_template(&self.meta_page_url_template, &[("page_name", page_name)])
+    }
+
+    /// Placeholders: `{page}` (starting from 1).
+    pub fn sitemap_url(&self, page: u32) -> String {
+        self.fill_template(&self.sitemap_url_template, &[("page", &page.to_string())])
+    }
+
+    /// Placeholders: `{format}` (`atom`, `rss` or `json`).
+    /// The query parameters selecting the feed are appended to the URL.
+    pub fn feed_url(&self, format: &str, query: &[(&str, &str)]) -> String {
+        let mut url = self.fill_template(&self.feed_url_template, &[("format", format)]);
+        for (name, value) in query {
+            url.push(if url.contains('?') { '&' } else { '?' });
+            url.push_str(&format!("{}={}", name, encode_path_segment(value)));
+        }
+        url
+    }
+}
+
+/// Percent-encodes everything but unreserved characters (RFC 3986).
+pub fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::new();
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
@@ -0,0 +1,144 @@
+
+use std::io::Cursor;
+
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits};
+use image::imageops::FilterType;
+
+use crate::api::v1::types::IconImage;
+use crate::limits;
+use crate::media::{self, MediaStore};
+use crate::sys_time;
+
+
+/// Square sizes (in pixels) in which icons are rendered.
+pub static ICON_SIZES: [u32; 3] = [64, 128, 256];
+
+/// Size of the icon linked from `AuthorSummary` and `ChannelSummary`.
+/// Queries building summaries select it with `author_summary_icon_query` and `channel_summary_icon_query`.
+pub static SUMMARY_ICON_SIZE: u32 = 128;
+
+/// SQL subquery selecting the hash of the summary icon of an author (or NULL),
+/// given the column holding the author's id, e.g. `author.id`.
+pub fn author_summary_icon_query(author_id_column: &str) -> String {
+    format!(
+        "(SELECT media.sha256 FROM author_icon, media WHERE author_icon.author_id = {} AND author_icon.size = {} AND author_icon.media_id = media.id)",
+        author_id_column, SUMMARY_ICON_SIZE,
+    )
+}
+
+/// SQL subquery selecting the hash of the summary icon of a channel (or NULL),
+/// given the column holding the channel's id, e.g. `channel.id`.
+pub fn channel_summary_icon_query(channel_id_column: &str) -> String {
+    format!(
+        "(SELECT media.sha256 FROM channel_icon, media WHERE channel_icon.channel_id = {} AND channel_icon.size = {} AND channel_icon.media_id = media.id)",
+        channel_id_column, SUMMARY_ICON_SIZE,
+    )
+}
+
+/// Decodes an uploaded image and renders it in all `ICON_SIZES` as PNG.
+/// Re-encoding drops all metadata (EXIF, comments, etc.) of the original.
+pub fn render_icons(data: &[u8]) -> anyhow::Result<Vec<(u32, Vec<u8>)>> {
+    if media::detect_mime_type(data).is_none() {
+        return Err(anyhow::anyhow!("Unsupported file type"));
+    }
+
+    let mut image_limits = Limits::default();
+    image_limits.max_image_width = Some(limits::MAX_ICON_SOURCE_DIMENSION);
+    image_limits.max_image_height = Some(limits::MAX_ICON_SOURCE_DIMENSION);
+
+    let mut reader = ImageReader::new(Cursor::new(data)).with_guessed_format()?;
+    reader.limits(image_limits);
+    let mut decoder = reader.into_decoder().map_err(|_| anyhow::anyhow!("Invalid image"))?;
+    let orientation = decoder.orientation()?;
+    let mut image = DynamicImage::from_decoder(decoder).map_err(|_| anyhow::anyhow!("Invalid image"))?;
+    image.apply_orientation(orientation);
+
+    let mut icons = Vec::new();
+    for size in ICON_SIZES {
+        let icon = image.resize_to_fill(size, size, FilterType::Lanczos3);
+        let mut png = Vec::new();
+        icon.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+        icons.push((size, png));
+    }
+    Ok(icons)
+}
+
+/// Stores rendered icons as media files uploaded by the given author.
+/// Returns the media ids by size.
+pub fn store_icons(conn: &rusqlite::Connection, media_store: &MediaStore, author_id: u32, icons: &[(u32, Vec<u8>)]) -> anyhow::Result<Vec<(u32, u32)>> {
+    let uploaded_date = sys_time::get_sys_time_in_secs();
+    let mut media_ids = Vec::new();
+    for (size, png) in icons {
+        let hash = media_store.store(png)?;
+        conn.execute(
+            "INSERT INTO media (sha256, mime_type, size, author_id, uploaded_date) VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT (sha256) DO UPDATE SET uploaded_date = excluded.uploaded_date",
+            (&hash, "image/png", png.len(), author_id, uploaded_date),
+        )?;
+        let media_id: u32 = conn.query_row(
+            "SELECT id FROM media WHERE sha256 = ?",
+            [&hash],
+            |row| row.get(0),
+        )?;
+        media_ids.push((*size, media_id));
+    }
+    Ok(media_ids)
+}
+
+/// Loads and renders the icons of an uploaded media file.
+/// The file must have been uploaded through `/api/v1/media/upload` first.
+pub fn load_and_render_icons(media_store: &MediaStore, sha256: &str) -> anyhow::Result<Vec<(u32, Vec<u8>)>> {
+    if !media::is_valid_sha256_hex(sha256) {
+        return Err(anyhow::anyhow!("Invalid icon hash"));
+    }
+    let data = media_store.load(sha256).map_err(|_| anyhow::anyhow!("Icon file not found"))?;
+    render_icons(&data)
+}
+
+/// Replaces the icons of an author. An empty list removes the icons.
+pub fn set_author_icons(conn: &rusqlite::Connection, author_id: u32, media_ids: &[(u32, u32)]) -> rusqlite::Result<()> {
+    conn.execute("DELETE FROM author_icon WHERE author_id = ?", [author_id])?;
+    for (size, media_id) in media_ids {
+        conn.execute(
+            "INSERT INTO author_icon (author_id, size, media_id) VALUES (?, ?, ?)",
+            (author_id, size, media_id),
+        )?;
+    }
+    Ok(())
+}
+
+/// Replaces the icons of a channel. An empty list removes the icons.
+pub fn set_channel_icons(conn: &rusqlite::Connection, channel_id: u32, media_ids: &[(u32, u32)]) -> rusqlite::Result<()> {
+    conn.execute("DELETE FROM channel_icon WHERE channel_id = ?", [channel_id])?;
+    for (size, media_id) in media_ids {
+        conn.execute(
+            "INSERT INTO channel_icon (channel_id, size, media_id) VALUES (?, ?, ?)",
+            (channel_id, size, media_id),
+        )?;
+    }
+    Ok(())
+}
+
+pub fn get_author_icons(conn: &rusqlite::Connection, author_id: u32) -> rusqlite::Result<Vec<IconImage>> {
+    let mut stmt = conn.prepare(
+        "SELECT author_icon.size, media.sha256 FROM author_icon, media WHERE author_icon.author_id = ? AND author_icon.media_id = media.id ORDER BY author_icon.size ASC"
+    )?;
+    let icons = stmt.query_map([author_id], |row| {
+        let size: u32 = row.get(0)?;
+        let sha256: String = row.get(1)?;
+        Ok(IconImage::new(size, &sha256))
+    })?.collect();
+    icons
+}
+
+pub fn get_channel_icons(conn: &rusqlite::Connection, channel_id: u32) -> rusqlite::Result<Vec<IconImage>> {
+    let mut stmt = conn.prepare(
+        "SELECT channel_icon.size, media.sha256 FROM channel_icon, media WHERE channel_icon.channel_id = ? AND channel_icon.media_id = media.id ORDER BY channel_icon.size ASC"
+    )?;
+    let icons = stmt.query_map([channel_id], |row| {
+        let size: u32 = row.get(0)?;
+        let sha256: String = row.get(1)?;
+        Ok(IconImage::new(size, &sha256))
+    })?.collect();
+    icons
+}
@@ -0,0 +1,77 @@
+
+use crate::api::v1::types::validate_content_label;
+use crate::limits;
+
+
+/// Validates the content labels and the content warning given by an author or an admin.
+pub fn validate_labels(labels: &[String], content_warning: Option<&str>) -> anyhow::Result<()> {
+    for label in labels {
+        validate_content_label(label)?;
+    }
+    if let Some(content_warning) = content_warning {
+        if content_warning.len() > limits::MAX_ITEM_DESCRIPTION_SIZE {
+            return Err(anyhow::anyhow!("Content warning is too long"));
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the labels of a post applied by its authors, or by admins.
+/// Labels applied by admins are kept when authors update theirs.
+pub fn set_post_labels(conn: &rusqlite::Connection, post_id: u32, labels: &[String], is_admin_label: bool) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM post_label WHERE post_id = ? AND is_admin_label = ?",
+        (post_id, is_admin_label),
+    )?;
+    for label in labels {
+        conn.execute(
+            "INSERT OR IGNORE INTO post_label (post_id, label, is_admin_label) VALUES (?, ?, ?)",
+            (post_id, label, is_admin_label),
+        )?;
+    }
+    Ok(())
+}
+
+/// Returns the labels of a post, whoever applied them.
+pub fn get_post_labels(conn: &rusqlite::Connection, post_id: u32) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT DISTINCT label FROM post_label WHERE post_id = ? ORDER BY label ASC")?;
+    let labels = stmt.query_map([post_id], |row| row.get(0))?.collect();
+    labels
+}
+
+/// Column of listings selecting the labels of a post, to be split with `split_labels()`.
+pub static POST_LABELS_COLUMN: &str = "(SELECT GROUP_CONCAT(DISTINCT post_label.label) FROM post_label WHERE post_label.post_id = post.id)";
+
+/// Splits the labels selected with `POST_LABELS_COLUMN` in listings.
+pub fn split_labels(labels: Option<String>) -> Vec<String> {
+    let mut labels: Vec<String> = labels.map(|labels| labels.split(',').map(|label| label.to_string()).collect()).unwrap_or_default();
+    labels.sort();
+    labels
+}
+
+/// Parses the `exclude_labels` query parameter of listings.
+/// Returns whether all labelled posts (including posts with a content warning) are excluded,
+/// and the excluded labels as a JSON array to be used with `json_each()`.
+pub fn parse_exclude_labels(exclude_labels: Option<&String>) -> anyhow::Result<(bool, String)> {
+    let exclude_labels = match exclude_labels.map(|labels| labels.as_str()) {
+        None | Some("") => return Ok((false, "[]".to_string())),
+        Some("all") => return Ok((true, "[]".to_string())),
+        Some(labels) => labels,
+    };
+
+    let labels: Vec<&str> = exclude_labels.split(',').collect();
+    for label in &labels {
+        validate_content_label(label)?;
+    }
+    Ok((false, serde_json::to_string(&labels)?))
+}
+
+/// Condition of listings excluding the posts as requested with `exclude_labels`.
+/// The parameters (e.g. `?2` and `?3`) take the values returned by `parse_exclude_labels()`.
+pub fn exclude_labels_condition(exclude_all_param: &str, exclude_labels_param: &str) -> String {
+    format!(
+        "NOT EXISTS (SELECT 1 FROM post_label WHERE post_label.post_id = post.id AND ({all} OR post_label.label IN (SELECT value FROM json_each({labels})))) AND NOT ({all} AND post.content_warning IS NOT NULL)",
+        all = exclude_all_param,
+        labels = exclude_labels_param,
+    )
+}
@@ -7,3 +7,18 @@ pub mod crypto;
 pub mod error_reporting;
 pub mod limits;
 pub mod db;
+pub mod markdown;
+pub mod drafts;
+pub mod slugs;
+pub mod media;
+pub mod icons;
+pub mod roles;
+pub mod translations;
+pub mod meta_pages;
+pub mod pins;
+pub mod labels;
+pub mod pagination;
+pub mod listings;
+pub mod frontend;
+pub mod feeds;
+pub mod sitemaps;
@@ -4,3 +4,19 @@ pub static MAX_PAGE_TEXT_SIZE: usize = 100_000; // 100kB
 pub static MAX_PAGE_TITLE_SIZE: usize = 1000;
 pub static MAX_ITEM_NAME_SIZE: usize = 100;
 pub static MAX_ITEM_DESCRIPTION_SIZE: usize = 1000;
+pub static MAX_MEDIA_SIZE: usize = 2_000_000; // 2MB
+pub static MAX_COMMENT_TEXT_SIZE: usize = 10_000; // 10kB
+
+// limits in pixels
+pub static MAX_ICON_SOURCE_DIMENSION: u32 = 4096;
+
+// limits in entries
+pub static MAX_PINNED_POSTS: usize = 10; // per channel
+pub static MAX_FEATURED_POSTS: usize = 100;
+pub static MAX_PAGE_SIZE: u32 = 1000; // per page of a listing
+pub static DEFAULT_PAGE_SIZE: u32 = 100;
+pub static MAX_FEED_ENTRIES: u32 = 50; // per feed
+pub static MAX_SITEMAP_URLS: u32 = 50_000; // per sitemap, as the sitemap protocol allows
+
+// limits in characters
+pub static FEED_EXCERPT_LENGTH: usize = 500;
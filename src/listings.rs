@@ -0,0 +1,10 @@
+
+/// Tables of the queries listing public posts (`post/list`, `channel/posts`, `tag/posts`, `author/posts`,
+/// pinned and featured posts, and feeds): revisions with their posts, channels, authors and current slugs.
+/// Each row is a revision; listings of posts group by `post.id` and select `MAX(revision.created_date)`
+/// for the latest revision. Other tables can be joined after these.
+pub static POST_LISTING_TABLES: &str = "channel, post, revision, author LEFT JOIN post_slug ON post_slug.post_id = post.id AND post_slug.is_current = 1";
+
+/// Conditions of the queries listing public posts, for `POST_LISTING_TABLES`.
+/// Deleted rows and posts that are not public are never listed.
+pub static POST_LISTING_CONDITIONS: &str = "channel.is_deleted = 0 AND post.is_deleted = 0 AND post.visibility = 'public' AND revision.is_deleted = 0 AND author.is_deleted = 0 AND channel.id = post.channel_id AND post.id = revision.post_id AND revision.author_id = author.id";
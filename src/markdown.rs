@@ -0,0 +1,106 @@
+
+use std::collections::{HashMap, HashSet};
+
+use pulldown_cmark::{Parser, Options, Event, html};
+
+
+/// Maximum number of rendered texts kept in the cache.
+/// The cache is simply cleared when it gets full.
+const MAX_CACHE_ENTRIES: usize = 1000;
+
+const ALLOWED_TAGS: &[&str] = &[
+    "p", "br", "hr",
+    "h1", "h2", "h3", "h4", "h5", "h6",
+    "blockquote", "pre", "code",
+    "em", "strong", "del", "sup", "sub",
+    "ul", "ol", "li",
+    "a", "img",
+    "table", "thead", "tbody", "tr", "th", "td",
+];
+
+const ALLOWED_URL_SCHEMES: &[&str] = &[
+    "http",
+    "https",
+    "mailto",
+];
+
+/// Renders Markdown (CommonMark) text into sanitized HTML.
+/// Only an allow-list of tags and attributes survives sanitization.
+pub fn render_markdown(text: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+
+    let parser = Parser::new_ext(text, options);
+    let mut unsafe_html = String::new();
+    html::push_html(&mut unsafe_html, parser);
+
+    let mut tag_attributes = HashMap::new();
+    tag_attributes.insert("a", HashSet::from(["href", "title"]));
+    tag_attributes.insert("img", HashSet::from(["src", "alt", "title"]));
+    tag_attributes.insert("ol", HashSet::from(["start"]));
+
+    ammonia::Builder::empty()
+        .tags(ALLOWED_TAGS.iter().copied().collect())
+        .clean_content_tags(HashSet::from(["script", "style"]))
+        .tag_attributes(tag_attributes)
+        .generic_attributes(HashSet::new())
+        .url_schemes(ALLOWED_URL_SCHEMES.iter().copied().collect())
+        .link_rel(Some("noopener noreferrer nofollow"))
+        .clean(&unsafe_html)
+        .to_string()
+}
+
+/// Renders the beginning of Markdown text as plain text, for excerpts.
+/// Whitespace is collapsed, and the text is cut at a word boundary if it is longer than `max_chars`.
+pub fn render_excerpt(text: &str, max_chars: usize) -> String {
+    let mut plain_text = String::new();
+    for event in Parser::new(text) {
+        match event {
+            Event::Text(text) | Event::Code(text) => plain_text.push_str(&text),
+            Event::SoftBreak | Event::HardBreak | Event::End(_) => plain_text.push(' '),
+            _ => {},
+        }
+    }
+    let plain_text = plain_text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if plain_text.chars().count() <= max_chars {
+        return plain_text;
+    }
+    let mut excerpt: String = plain_text.chars().take(max_chars).collect();
+    if let Some(index) = excerpt.rfind(' ') {
+        excerpt.truncate(index);
+    }
+    excerpt.push('…');
+    excerpt
+}
+
+/// MarkdownCache keeps rendered HTML of immutable texts (e.g. revisions)
+/// keyed by their UUIDs.
+#[derive(Debug, Default)]
+pub struct MarkdownCache {
+    entries: HashMap<String, String>,
+}
+
+impl MarkdownCache {
+    pub fn new() -> MarkdownCache {
+        MarkdownCache {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// The text must always be the same for the same key.
+    pub fn render(&mut self, key: &str, text: &str) -> String {
+        if let Some(html) = self.entries.get(key) {
+            return html.clone();
+        }
+
+        if self.entries.len() >= MAX_CACHE_ENTRIES {
+            self.entries.clear();
+        }
+
+        let html = render_markdown(text);
+        self.entries.insert(key.to_string(), html.clone());
+        html
+    }
+}
@@ -0,0 +1,168 @@
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+use regex::Regex;
+use sha2::{Sha256, Digest};
+
+use crate::state::AppState;
+use crate::sys_time;
+
+
+/// Interval at which unreferenced media files are garbage-collected.
+pub static MEDIA_GC_INTERVAL_SECS: u64 = 3600;
+
+/// Media files younger than this are never garbage-collected,
+/// so that they can be uploaded before the posts referencing them.
+pub static MEDIA_GC_GRACE_PERIOD_SECS: u64 = 86400;
+
+/// URL path under which media files are served. Followed by the SHA-256 hash in hex.
+pub static MEDIA_URL_PREFIX: &str = "/api/v1/media/";
+
+/// Detects the MIME type of a file from its magic bytes.
+/// Returns `None` for types not in the allow-list.
+pub fn detect_mime_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if data.starts_with(b"\xff\xd8\xff") {
+        Some("image/jpeg")
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+pub fn is_valid_sha256_hex(hash: &str) -> bool {
+    let re = Regex::new(r"^[0-9a-f]{64}$").unwrap();
+    re.is_match(hash)
+}
+
+/// MediaStore stores files on local disk, addressed by their SHA-256 hashes.
+#[derive(Debug, Clone)]
+pub struct MediaStore {
+    root: PathBuf,
+}
+
+impl MediaStore {
+    pub fn new(root: PathBuf) -> MediaStore {
+        MediaStore {
+            root,
+        }
+    }
+
+    pub fn new_from_env() -> MediaStore {
+        let media_path = env::var("MEDIA_PATH").unwrap_or("".to_string());
+        let media_path = if media_path.is_empty() {
+            log::warn!("MEDIA_PATH not set, using ./media");
+            "media".to_string()
+        } else {
+            log::info!("Using media directory at {}", media_path);
+            media_path
+        };
+
+        MediaStore::new(PathBuf::from(media_path))
+    }
+
+    fn path(&self, hash: &str) -> PathBuf {
+        self.root.join(&hash[0..2]).join(hash)
+    }
+
+    /// Stores a file and returns its SHA-256 hash in hex.
+    pub fn store(&self, data: &[u8]) -> anyhow::Result<String> {
+        let hash = hex::encode(Sha256::digest(data));
+        let path = self.path(&hash);
+        if path.exists() {
+            return Ok(hash);
+        }
+
+        let dir = path.parent().ok_or_else(|| anyhow::anyhow!("Invalid media path"))?;
+        fs::create_dir_all(dir)?;
+
+        // write to a temporary file first, so that readers never see partial files
+        let tmp_path = dir.join(format!("{}.{}.tmp", hash, uuid::Uuid::new_v4()));
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(data)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &path)?;
+
+        Ok(hash)
+    }
+
+    pub fn load(&self, hash: &str) -> anyhow::Result<Vec<u8>> {
+        if !is_valid_sha256_hex(hash) {
+            return Err(anyhow::anyhow!("Invalid hash"));
+        }
+        Ok(fs::read(self.path(hash))?)
+    }
+
+    pub fn remove(&self, hash: &str) -> anyhow::Result<()> {
+        if !is_valid_sha256_hex(hash) {
+            return Err(anyhow::anyhow!("Invalid hash"));
+        }
+        match fs::remove_file(self.path(hash)) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Records the media files referenced in a text as used by a post.
+/// References are never removed, because old revisions stay readable.
+pub fn record_post_media(conn: &rusqlite::Connection, post_id: u32, text: &str) -> rusqlite::Result<()> {
+    let re = Regex::new(&format!("{}([0-9a-f]{{64}})", regex::escape(MEDIA_URL_PREFIX))).unwrap();
+    for captures in re.captures_iter(text) {
+        conn.execute(
+            "INSERT OR IGNORE INTO post_media (post_id, media_id) SELECT ?, id FROM media WHERE sha256 = ?",
+            (post_id, &captures[1]),
+        )?;
+    }
+    Ok(())
+}
+
+/// Deletes media files that are not referenced by any non-deleted post, draft or icon.
+/// Returns the number of deleted files.
+pub fn collect_garbage(state: &AppState) -> anyhow::Result<usize> {
+    let now = sys_time::get_sys_time_in_secs();
+    let mut db_connection = state.db_connection.lock().unwrap();
+    let trx = db_connection.transaction()?;
+
+    let mut stmt = trx.prepare(
+        "
+            SELECT media.id, media.sha256 FROM media
+            WHERE media.uploaded_date < ?
+            AND NOT EXISTS (SELECT 1 FROM post_media, post WHERE post_media.media_id = media.id AND post_media.post_id = post.id AND post.is_deleted = 0)
+            AND NOT EXISTS (SELECT 1 FROM draft WHERE instr(draft.draft_text, media.sha256) > 0)
+            AND NOT EXISTS (SELECT 1 FROM author_icon WHERE author_icon.media_id = media.id)
+            AND NOT EXISTS (SELECT 1 FROM channel_icon WHERE channel_icon.media_id = media.id)
+        "
+    )?;
+    let mut rows = stmt.query([now.saturating_sub(MEDIA_GC_GRACE_PERIOD_SECS)])?;
+
+    let mut unreferenced = Vec::new();
+    while let Some(row) = rows.next()? {
+        let media_id: u32 = row.get(0)?;
+        let hash: String = row.get(1)?;
+        unreferenced.push((media_id, hash));
+    }
+
+    drop(rows);
+    drop(stmt);
+
+    for (media_id, _) in &unreferenced {
+        trx.execute("DELETE FROM media WHERE id = ?", [media_id])?;
+    }
+    trx.commit()?;
+
+    // files are removed only after the rows are gone
+    for (_, hash) in &unreferenced {
+        state.media_store.remove(hash)?;
+    }
+
+    Ok(unreferenced.len())
+}
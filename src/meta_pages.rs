@@ -0,0 +1,73 @@
+
+use rusqlite::OptionalExtension;
+
+
+/// Language variants to try for a requested language, most specific first.
+/// `zh-Hant-TW` falls back to `zh-Hant`, `zh` and then the default variant (`""`).
+fn fallback_languages(lang: Option<&str>) -> Vec<String> {
+    let mut langs = Vec::new();
+    if let Some(lang) = lang {
+        let mut lang = lang;
+        loop {
+            langs.push(lang.to_string());
+            match lang.rfind('-') {
+                Some(index) => lang = &lang[..index],
+                None => break,
+            }
+        }
+    }
+    langs.push(String::new());
+    langs
+}
+
+/// Finds the variant of a meta page that best matches the requested language.
+/// If neither the language nor the default variant exists, any variant is returned.
+/// Returns the id and the language code of the variant.
+pub fn find_meta_page(conn: &rusqlite::Connection, page_name: &str, lang: Option<&str>) -> rusqlite::Result<Option<(u32, String)>> {
+    for lang in fallback_languages(lang) {
+        let id = conn.query_row(
+            "SELECT id FROM meta_page WHERE page_name = ? AND language_code = ? AND is_deleted = 0",
+            (page_name, &lang),
+            |row| row.get(0),
+        ).optional()?;
+        if let Some(id) = id {
+            return Ok(Some((id, lang)));
+        }
+    }
+
+    conn.query_row(
+        "SELECT id, language_code FROM meta_page WHERE page_name = ? AND is_deleted = 0 ORDER BY language_code ASC LIMIT 1",
+        [page_name],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    ).optional()
+}
+
+/// Lists the languages a meta page is available in. The default variant is `""`.
+pub fn get_meta_page_languages(conn: &rusqlite::Connection, page_name: &str) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT language_code FROM meta_page WHERE page_name = ? AND is_deleted = 0 ORDER BY language_code ASC")?;
+    let langs = stmt.query_map([page_name], |row| row.get(0))?.collect();
+    langs
+}
+
+/// Creates or updates a variant of a meta page and records the content as a new revision.
+/// A deleted variant is restored.
+/// Returns the uuid of the revision.
+pub fn save_meta_page(conn: &rusqlite::Connection, page_name: &str, lang: &str, title: &str, text: &str, updated_date: u64) -> rusqlite::Result<String> {
+    conn.execute(
+        "INSERT INTO meta_page (page_name, language_code, title, page_text, updated_date) VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT (page_name, language_code) DO UPDATE SET title = excluded.title, page_text = excluded.page_text, updated_date = excluded.updated_date, is_deleted = 0",
+        (page_name, lang, title, text, updated_date),
+    )?;
+    let meta_page_id: u32 = conn.query_row(
+        "SELECT id FROM meta_page WHERE page_name = ? AND language_code = ?",
+        (page_name, lang),
+        |row| row.get(0),
+    )?;
+
+    let revision_uuid = uuid::Uuid::new_v4().to_string();
+    conn.execute(
+        "INSERT INTO meta_page_revision (uuid, meta_page_id, created_date, title, page_text) VALUES (?, ?, ?, ?, ?)",
+        (&revision_uuid, meta_page_id, updated_date, title, text),
+    )?;
+    Ok(revision_uuid)
+}
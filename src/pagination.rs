@@ -0,0 +1,104 @@
+
+use std::collections::HashMap;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD as cursor_engine};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::limits;
+
+
+/// Position in a listing: the sort key (usually a date) and a unique id breaking ties.
+/// Listings are ordered by `key` and `id`, both descending unless noted otherwise.
+#[derive(Debug, Clone)]
+pub struct Cursor<T> {
+    pub key: u64,
+    pub id: T,
+}
+
+impl<T: Serialize + DeserializeOwned> Cursor<T> {
+    pub fn new(key: u64, id: T) -> Cursor<T> {
+        Cursor { key, id }
+    }
+
+    /// Encodes the cursor as an opaque URL-safe string.
+    pub fn encode(&self) -> String {
+        let json = serde_json::json!([self.key, self.id]).to_string();
+        cursor_engine.encode(json.as_bytes())
+    }
+
+    pub fn decode(cursor: &str) -> anyhow::Result<Cursor<T>> {
+        let invalid = || anyhow::anyhow!("Invalid cursor");
+        let json = cursor_engine.decode(cursor).map_err(|_| invalid())?;
+        let (key, id): (u64, T) = serde_json::from_slice(&json).map_err(|_| invalid())?;
+        Ok(Cursor { key, id })
+    }
+}
+
+/// The `limit` and `cursor` query parameters of a listing.
+/// Without either of them, the listing is returned as a plain array of at most
+/// `MAX_PAGE_SIZE` items, as v1 clients expect.
+#[derive(Debug, Clone)]
+pub struct PageParams<T> {
+    paginated: bool,
+    limit: u32,
+    cursor: Option<Cursor<T>>,
+}
+
+impl<T: Serialize + DeserializeOwned> PageParams<T> {
+    pub fn from_query(params: &HashMap<String, String>) -> anyhow::Result<PageParams<T>> {
+        let limit = params.get("limit").map(|limit| {
+            let limit: u32 = limit.parse().map_err(|_| anyhow::anyhow!("Invalid limit"))?;
+            if limit == 0 || limit > limits::MAX_PAGE_SIZE {
+                return Err(anyhow::anyhow!("Invalid limit"));
+            }
+            Ok(limit)
+        }).transpose()?;
+        let cursor = params.get("cursor").map(|cursor| Cursor::decode(cursor)).transpose()?;
+
+        Ok(PageParams {
+            paginated: limit.is_some() || cursor.is_some(),
+            limit: limit.unwrap_or(if cursor.is_some() { limits::DEFAULT_PAGE_SIZE } else { limits::MAX_PAGE_SIZE }),
+            cursor,
+        })
+    }
+
+    /// Sort key of the cursor, to be compared in the query. `None` on the first page.
+    pub fn cursor_key(&self) -> Option<u64> {
+        self.cursor.as_ref().map(|cursor| cursor.key)
+    }
+
+    /// Id of the cursor, to be compared in the query. `None` on the first page.
+    pub fn cursor_id(&self) -> Option<&T> {
+        self.cursor.as_ref().map(|cursor| &cursor.id)
+    }
+
+    /// Number of rows to query. One more row than the page holds tells whether there is a next page.
+    pub fn query_limit(&self) -> u32 {
+        if self.paginated {
+            self.limit + 1
+        } else {
+            self.limit
+        }
+    }
+
+    /// Builds the response from the queried rows and their cursors.
+    /// Paginated responses are `{"items": [...], "next_cursor": "<cursor or null>"}`.
+    pub fn into_response(self, mut items: Vec<(Cursor<T>, serde_json::Value)>) -> serde_json::Value {
+        if !self.paginated {
+            let items: Vec<serde_json::Value> = items.into_iter().map(|(_, item)| item).collect();
+            return serde_json::json!(items);
+        }
+
+        let next_cursor = if items.len() > self.limit as usize {
+            items.truncate(self.limit as usize);
+            items.last().map(|(cursor, _)| cursor.encode())
+        } else {
+            None
+        };
+        let items: Vec<serde_json::Value> = items.into_iter().map(|(_, item)| item).collect();
+        serde_json::json!({
+            "items": items,
+            "next_cursor": next_cursor,
+        })
+    }
+}
@@ -0,0 +1,67 @@
+
+use crate::limits;
+
+
+/// Replaces the pinned posts of a channel, in the given order.
+/// The posts must be public and belong to the channel.
+pub fn set_channel_pins(conn: &rusqlite::Connection, channel_id: u32, post_uuids: &[String]) -> anyhow::Result<()> {
+    if post_uuids.len() > limits::MAX_PINNED_POSTS {
+        return Err(anyhow::anyhow!("Too many pinned posts"));
+    }
+
+    conn.execute("DELETE FROM channel_pin WHERE channel_id = ?", [channel_id])?;
+    for (position, post_uuid) in post_uuids.iter().enumerate() {
+        if post_uuids[..position].contains(post_uuid) {
+            return Err(anyhow::anyhow!("Duplicate post"));
+        }
+        let (post_id, visibility) = conn.query_row(
+            "SELECT id, visibility FROM post WHERE uuid = ? AND channel_id = ? AND is_deleted = 0",
+            (post_uuid, channel_id),
+            |row| {
+                let post_id: u32 = row.get(0)?;
+                let visibility: String = row.get(1)?;
+                Ok((post_id, visibility))
+            },
+        ).map_err(|_| anyhow::anyhow!("Post not found in the channel"))?;
+        if visibility != "public" {
+            return Err(anyhow::anyhow!("Only public posts can be pinned"));
+        }
+        conn.execute(
+            "INSERT INTO channel_pin (channel_id, post_id, position) VALUES (?, ?, ?)",
+            (channel_id, post_id, position),
+        )?;
+    }
+    Ok(())
+}
+
+/// Replaces the instance-wide featured posts, in the given order.
+/// The posts must be public.
+pub fn set_featured_posts(conn: &rusqlite::Connection, post_uuids: &[String]) -> anyhow::Result<()> {
+    if post_uuids.len() > limits::MAX_FEATURED_POSTS {
+        return Err(anyhow::anyhow!("Too many featured posts"));
+    }
+
+    conn.execute("DELETE FROM featured_post", [])?;
+    for (position, post_uuid) in post_uuids.iter().enumerate() {
+        if post_uuids[..position].contains(post_uuid) {
+            return Err(anyhow::anyhow!("Duplicate post"));
+        }
+        let (post_id, visibility) = conn.query_row(
+            "SELECT post.id, post.visibility FROM post, channel WHERE post.uuid = ? AND post.is_deleted = 0 AND post.channel_id = channel.id AND channel.is_deleted = 0",
+            [post_uuid],
+            |row| {
+                let post_id: u32 = row.get(0)?;
+                let visibility: String = row.get(1)?;
+                Ok((post_id, visibility))
+            },
+        ).map_err(|_| anyhow::anyhow!("Post not found"))?;
+        if visibility != "public" {
+            return Err(anyhow::anyhow!("Only public posts can be featured"));
+        }
+        conn.execute(
+            "INSERT INTO featured_post (post_id, position) VALUES (?, ?)",
+            (post_id, position),
+        )?;
+    }
+    Ok(())
+}
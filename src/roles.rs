@@ -0,0 +1,68 @@
+
+use rusqlite::OptionalExtension;
+
+use crate::api::v1::types::ChannelRole;
+
+
+/// Returns the role of an author in a channel.
+/// Fails if the author is not an author of the channel.
+pub fn get_channel_role(conn: &rusqlite::Connection, channel_id: u32, author_id: u32) -> anyhow::Result<ChannelRole> {
+    let role: String = conn.query_row(
+        "SELECT role FROM channel_author WHERE channel_id = ? AND author_id = ?",
+        (channel_id, author_id),
+        |row| row.get(0),
+    ).map_err(|_| anyhow::anyhow!("Not an author of the channel"))?;
+    role.parse()
+}
+
+/// Fails unless the author is an owner of the channel.
+pub fn check_channel_manageable(conn: &rusqlite::Connection, channel_id: u32, author_id: u32) -> anyhow::Result<()> {
+    if !get_channel_role(conn, channel_id, author_id)?.can_manage_channel() {
+        return Err(anyhow::anyhow!("Only owners can manage the channel"));
+    }
+    Ok(())
+}
+
+/// The author of the first revision of a post is regarded as its creator.
+pub fn is_post_creator(conn: &rusqlite::Connection, post_id: u32, author_id: u32) -> rusqlite::Result<bool> {
+    let creator_id: Option<u32> = conn.query_row(
+        "SELECT author_id FROM revision WHERE post_id = ? ORDER BY created_date ASC, id ASC LIMIT 1",
+        [post_id],
+        |row| row.get(0),
+    ).optional()?;
+    Ok(creator_id == Some(author_id))
+}
+
+/// Fails unless the author may edit (or delete) the post.
+/// Contributors may only edit the posts they created.
+pub fn check_post_editable(conn: &rusqlite::Connection, channel_id: u32, post_id: u32, author_id: u32) -> anyhow::Result<()> {
+    let role = get_channel_role(conn, channel_id, author_id)?;
+    if !role.can_edit_all_posts() && !is_post_creator(conn, post_id, author_id)? {
+        return Err(anyhow::anyhow!("Contributors can only edit their own posts"));
+    }
+    Ok(())
+}
+
+/// Fails unless the author may access the draft.
+/// Contributors may only access the drafts they last edited.
+pub fn check_draft_editable(conn: &rusqlite::Connection, draft_id: u32, author_id: u32) -> anyhow::Result<()> {
+    let (channel_id, draft_author_id): (u32, u32) = conn.query_row(
+        "SELECT channel_id, author_id FROM draft WHERE id = ?",
+        [draft_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let role = get_channel_role(conn, channel_id, author_id)?;
+    if !role.can_edit_all_posts() && draft_author_id != author_id {
+        return Err(anyhow::anyhow!("Contributors can only access their own drafts"));
+    }
+    Ok(())
+}
+
+/// Counts the owners of a channel who are not deleted.
+pub fn count_channel_owners(conn: &rusqlite::Connection, channel_id: u32) -> rusqlite::Result<u32> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM channel_author, author WHERE channel_author.channel_id = ? AND channel_author.role = 'owner' AND channel_author.author_id = author.id AND author.is_deleted = 0",
+        [channel_id],
+        |row| row.get(0),
+    )
+}
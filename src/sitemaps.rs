@@ -0,0 +1,141 @@
+
+use crate::feeds::escape_xml;
+use crate::frontend::Frontend;
+use crate::limits;
+use crate::listings;
+use crate::sys_time;
+
+
+/// Sitemaps are read by crawlers a few times a day at most.
+pub static SITEMAP_CACHE_CONTROL: &str = "public, max-age=3600";
+
+/// Every page listed in sitemaps, in a stable order: meta pages, channels, authors and posts.
+/// Columns: kind, id, then the values the URL is built from, then the last modification date.
+///
+/// * Meta pages are modified when any of their variants is updated.
+/// * Channels are modified when they are created and when any of their public posts is updated.
+/// * Authors are modified when they register and when they write a revision of a public post.
+/// * Posts are modified with every revision, including minor edits.
+///   Posts are the ones other listings of public posts show (`listings::POST_LISTING_CONDITIONS`).
+fn sitemap_urls_query() -> String {
+    format!(
+        "
+            SELECT 0 AS kind, 0 AS id, page_name AS value1, NULL AS value2, NULL AS value3, MAX(updated_date) AS lastmod
+            FROM meta_page
+            WHERE is_deleted = 0
+            GROUP BY page_name
+
+            UNION ALL
+
+            SELECT 1, channel.id, channel.handle, NULL, NULL,
+            MAX(channel.created_date, COALESCE((
+                SELECT MAX(post.updated_date) FROM post
+                WHERE post.channel_id = channel.id AND post.is_deleted = 0 AND post.visibility = 'public'
+            ), 0))
+            FROM channel
+            WHERE channel.is_deleted = 0
+
+            UNION ALL
+
+            SELECT 2, author.id, author.uuid, NULL, NULL,
+            MAX(author.registered_date, COALESCE((
+                SELECT MAX(revision.created_date) FROM revision, post, channel
+                WHERE revision.author_id = author.id AND revision.is_deleted = 0
+                AND post.id = revision.post_id AND post.is_deleted = 0 AND post.visibility = 'public'
+                AND channel.id = post.channel_id AND channel.is_deleted = 0
+            ), 0))
+            FROM author
+            WHERE author.is_deleted = 0
+
+            UNION ALL
+
+            SELECT 3, post.id, channel.handle, post.uuid, post_slug.slug, MAX(revision.created_date)
+            FROM {post_tables}
+            WHERE {post_conditions}
+            GROUP BY post.id
+        ",
+        post_tables = listings::POST_LISTING_TABLES,
+        post_conditions = listings::POST_LISTING_CONDITIONS,
+    )
+}
+
+#[derive(Debug, Clone)]
+pub struct SitemapUrl {
+    pub loc: String,
+    pub lastmod: u64,
+}
+
+/// Last modification dates of the sitemaps, one per chunk of `MAX_SITEMAP_URLS` URLs.
+/// There is always at least one sitemap; it has no date if it is empty.
+pub fn load_sitemap_dates(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<Option<u64>>> {
+    let mut stmt = conn.prepare(&format!(
+        "
+            SELECT (row_number - 1) / ?1 AS page, MAX(lastmod) FROM (
+                SELECT lastmod, ROW_NUMBER() OVER (ORDER BY kind, id, value1) AS row_number FROM ({})
+            )
+            GROUP BY page
+            ORDER BY page ASC
+        ",
+        sitemap_urls_query(),
+    ))?;
+    let mut dates = stmt.query_map([limits::MAX_SITEMAP_URLS], |row| row.get(1))?.collect::<rusqlite::Result<Vec<Option<u64>>>>()?;
+    if dates.is_empty() {
+        dates.push(None);
+    }
+    Ok(dates)
+}
+
+/// Loads the URLs of a sitemap. Pages start from 1.
+pub fn load_sitemap(conn: &rusqlite::Connection, frontend: &Frontend, page: u32) -> rusqlite::Result<Vec<SitemapUrl>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT kind, value1, value2, value3, lastmod FROM ({}) ORDER BY kind, id, value1 LIMIT ?1 OFFSET ?2",
+        sitemap_urls_query(),
+    ))?;
+    let offset = (page.saturating_sub(1) as u64) * (limits::MAX_SITEMAP_URLS as u64);
+    let urls = stmt.query_map((limits::MAX_SITEMAP_URLS, offset), |row| {
+        let kind: u32 = row.get(0)?;
+        let value1: String = row.get(1)?;
+        let value2: Option<String> = row.get(2)?;
+        let value3: Option<String> = row.get(3)?;
+        let lastmod: u64 = row.get(4)?;
+
+        let loc = match kind {
+            0 => frontend.meta_page_url(&value1),
+            1 => frontend.channel_url(&value1),
+            2 => frontend.author_url(&value1),
+            _ => frontend.post_url(&value1, value2.as_deref().unwrap_or_default(), value3.as_deref()),
+        };
+        Ok(SitemapUrl { loc, lastmod })
+    })?.collect();
+    urls
+}
+
+/// Renders a sitemap (sitemaps.org protocol 0.9).
+pub fn render_sitemap(urls: &[SitemapUrl]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for url in urls {
+        xml.push_str("  <url>\n");
+        xml.push_str(&format!("    <loc>{}</loc>\n", escape_xml(&url.loc)));
+        xml.push_str(&format!("    <lastmod>{}</lastmod>\n", sys_time::format_rfc3339(url.lastmod)));
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// Renders a sitemap index linking to the sitemaps through the frontend.
+pub fn render_sitemap_index(frontend: &Frontend, dates: &[Option<u64>]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for (index, date) in dates.iter().enumerate() {
+        xml.push_str("  <sitemap>\n");
+        xml.push_str(&format!("    <loc>{}</loc>\n", escape_xml(&frontend.sitemap_url(index as u32 + 1))));
+        if let Some(date) = date {
+            xml.push_str(&format!("    <lastmod>{}</lastmod>\n", sys_time::format_rfc3339(*date)));
+        }
+        xml.push_str("  </sitemap>\n");
+    }
+    xml.push_str("</sitemapindex>\n");
+    xml
+}
@@ -0,0 +1,70 @@
+
+/// Returns the current slug of a post.
+pub fn get_post_slug(conn: &rusqlite::Connection, post_id: u32) -> rusqlite::Result<Option<String>> {
+    let mut stmt = conn.prepare("SELECT slug FROM post_slug WHERE post_id = ? AND is_current = 1")?;
+    let mut rows = stmt.query([post_id])?;
+
+    if let Some(row) = rows.next()? {
+        let slug: String = row.get(0)?;
+        Ok(Some(slug))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Sets the current slug of a post. `None` removes the slug.
+/// The old slug is kept as a redirect alias.
+///
+/// Fails if the slug is the current slug of another post in the same channel.
+/// Aliases of other posts are taken over.
+pub fn set_post_slug(conn: &rusqlite::Connection, post_id: u32, channel_id: u32, slug: Option<&str>) -> anyhow::Result<()> {
+    let current_slug = get_post_slug(conn, post_id)?;
+    if current_slug.as_deref() == slug {
+        return Ok(());
+    }
+
+    conn.execute(
+        "UPDATE post_slug SET is_current = 0 WHERE post_id = ? AND is_current = 1",
+        [post_id],
+    )?;
+
+    let slug = if let Some(slug) = slug {
+        slug
+    } else {
+        return Ok(());
+    };
+
+    let existing = conn.query_row(
+        "SELECT post_slug.id, post_slug.post_id, post_slug.is_current, post.is_deleted FROM post_slug, post WHERE post_slug.channel_id = ? AND post_slug.slug = ? AND post_slug.post_id = post.id",
+        (channel_id, slug),
+        |row| {
+            let slug_id: u32 = row.get(0)?;
+            let slug_post_id: u32 = row.get(1)?;
+            let is_current: bool = row.get(2)?;
+            let is_deleted: bool = row.get(3)?;
+            Ok((slug_id, slug_post_id, is_current, is_deleted))
+        }
+    );
+
+    match existing {
+        Ok((slug_id, slug_post_id, _, _)) if slug_post_id == post_id => {
+            conn.execute("UPDATE post_slug SET is_current = 1 WHERE id = ?", [slug_id])?;
+            return Ok(());
+        },
+        Ok((_, _, true, false)) => {
+            return Err(anyhow::anyhow!("Slug is already in use"));
+        },
+        Ok((slug_id, _, _, _)) => {
+            conn.execute("DELETE FROM post_slug WHERE id = ?", [slug_id])?;
+        },
+        Err(rusqlite::Error::QueryReturnedNoRows) => {},
+        Err(e) => return Err(e.into()),
+    }
+
+    conn.execute(
+        "INSERT INTO post_slug (post_id, channel_id, slug) VALUES (?, ?, ?)",
+        (post_id, channel_id, slug),
+    )?;
+
+    Ok(())
+}
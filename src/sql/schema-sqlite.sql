@@ -40,7 +40,9 @@ CREATE TABLE IF NOT EXISTS `channel` (
   created_date INTEGER NOT NULL, -- seconds since UNIX epoch
   is_deleted INTEGER NOT NULL DEFAULT 0,
   description_text BLOB NOT NULL DEFAULT '',
-  language_code BLOB NOT NULL DEFAULT ''
+  language_code BLOB NOT NULL DEFAULT '',
+  comments_enabled INTEGER NOT NULL DEFAULT 1,
+  license BLOB NOT NULL DEFAULT 'LicenseRef-All-Rights-Reserved' -- SPDX identifier
 );
 
 CREATE UNIQUE INDEX IF NOT EXISTS `index_channel_uuid` ON `channel` (
@@ -55,6 +57,7 @@ CREATE TABLE IF NOT EXISTS `channel_author` (
   id INTEGER PRIMARY KEY,
   channel_id INTEGER NOT NULL,
   author_id INTEGER NOT NULL,
+  role BLOB NOT NULL DEFAULT 'owner', -- 'owner', 'editor' or 'contributor'
   FOREIGN KEY(channel_id) REFERENCES channel(id) ON DELETE CASCADE,
   FOREIGN KEY(author_id) REFERENCES author(id) ON DELETE CASCADE
 );
@@ -72,6 +75,12 @@ CREATE TABLE IF NOT EXISTS `post` (
   uuid BLOB UNIQUE NOT NULL,
   channel_id INTEGER NOT NULL,
   is_deleted INTEGER NOT NULL DEFAULT 0,
+  license BLOB, -- SPDX identifier, NULL for the channel's license
+  language_code BLOB, -- BCP 47 language tag, NULL for the channel's language
+  content_warning BLOB, -- free text, NULL if none
+  visibility BLOB NOT NULL DEFAULT 'public', -- 'public', 'unlisted' or 'private'
+  published_date INTEGER NOT NULL DEFAULT 0, -- seconds since UNIX epoch, date of the first revision
+  updated_date INTEGER NOT NULL DEFAULT 0, -- seconds since UNIX epoch, date of the latest revision that is not a minor edit
   FOREIGN KEY(channel_id) REFERENCES channel(id) ON DELETE CASCADE
 );
 
@@ -90,6 +99,7 @@ CREATE TABLE IF NOT EXISTS `revision` (
   author_id INTEGER NOT NULL,
   created_date INTEGER NOT NULL, -- seconds since UNIX epoch
   is_deleted INTEGER NOT NULL DEFAULT 0,
+  is_minor_edit INTEGER NOT NULL DEFAULT 0,
   title BLOB NOT NULL,
   revision_text BLOB NOT NULL,
   FOREIGN KEY(post_id) REFERENCES post(id) ON DELETE CASCADE,
@@ -130,12 +140,281 @@ CREATE UNIQUE INDEX IF NOT EXISTS `index_post_tag` ON `post_tag` (
 
 CREATE TABLE IF NOT EXISTS `meta_page` (
   id INTEGER PRIMARY KEY,
-  page_name BLOB UNIQUE NOT NULL,
+  page_name BLOB NOT NULL,
+  language_code BLOB NOT NULL DEFAULT '', -- BCP 47 language tag, '' for the default variant
   title BLOB NOT NULL,
   updated_date INTEGER NOT NULL, -- seconds since UNIX epoch
-  page_text BLOB NOT NULL DEFAULT ''
+  page_text BLOB NOT NULL DEFAULT '',
+  is_deleted INTEGER NOT NULL DEFAULT 0 -- deleted variants keep their revisions and can be restored
 );
 
-CREATE UNIQUE INDEX IF NOT EXISTS `index_meta_page_page_name` ON `meta_page` (
-  page_name
+CREATE UNIQUE INDEX IF NOT EXISTS `index_meta_page_page_name_language_code` ON `meta_page` (
+  page_name,
+  language_code
+);
+
+CREATE TABLE IF NOT EXISTS `meta_page_revision` (
+  id INTEGER PRIMARY KEY,
+  uuid BLOB UNIQUE NOT NULL,
+  meta_page_id INTEGER NOT NULL,
+  created_date INTEGER NOT NULL, -- seconds since UNIX epoch
+  title BLOB NOT NULL,
+  page_text BLOB NOT NULL DEFAULT '',
+  FOREIGN KEY(meta_page_id) REFERENCES meta_page(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS `index_meta_page_revision_meta_page_id` ON `meta_page_revision` (
+  meta_page_id
+);
+
+CREATE TABLE IF NOT EXISTS `draft` (
+  id INTEGER PRIMARY KEY,
+  uuid BLOB UNIQUE NOT NULL,
+  channel_id INTEGER NOT NULL,
+  post_id INTEGER, -- NULL for drafts of new posts
+  author_id INTEGER NOT NULL,
+  updated_date INTEGER NOT NULL, -- seconds since UNIX epoch
+  publish_date INTEGER, -- seconds since UNIX epoch, NULL if not scheduled
+  title BLOB NOT NULL,
+  draft_text BLOB NOT NULL,
+  slug BLOB, -- post settings applied when published, NULL to keep the post's current one
+  license BLOB,
+  language_code BLOB,
+  labels BLOB, -- JSON array of content labels
+  content_warning BLOB,
+  visibility BLOB,
+  FOREIGN KEY(channel_id) REFERENCES channel(id) ON DELETE CASCADE,
+  FOREIGN KEY(post_id) REFERENCES post(id) ON DELETE CASCADE,
+  FOREIGN KEY(author_id) REFERENCES author(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS `index_draft_uuid` ON `draft` (
+  uuid
+);
+
+CREATE INDEX IF NOT EXISTS `index_draft_channel_id` ON `draft` (
+  channel_id
+);
+
+CREATE INDEX IF NOT EXISTS `index_draft_author_id` ON `draft` (
+  author_id
+);
+
+CREATE INDEX IF NOT EXISTS `index_draft_publish_date` ON `draft` (
+  publish_date
+);
+
+CREATE TABLE IF NOT EXISTS `draft_tag` (
+  id INTEGER PRIMARY KEY,
+  draft_id INTEGER NOT NULL,
+  name BLOB NOT NULL,
+  FOREIGN KEY(draft_id) REFERENCES draft(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS `index_draft_tag_draft_id` ON `draft_tag` (
+  draft_id
+);
+
+CREATE TABLE IF NOT EXISTS `post_slug` (
+  id INTEGER PRIMARY KEY,
+  post_id INTEGER NOT NULL,
+  channel_id INTEGER NOT NULL,
+  slug BLOB NOT NULL,
+  is_current INTEGER NOT NULL DEFAULT 1, -- 0 for old slugs kept as redirect aliases
+  FOREIGN KEY(post_id) REFERENCES post(id) ON DELETE CASCADE,
+  FOREIGN KEY(channel_id) REFERENCES channel(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS `index_post_slug_channel_id_slug` ON `post_slug` (
+  channel_id,
+  slug
+);
+
+CREATE INDEX IF NOT EXISTS `index_post_slug_post_id` ON `post_slug` (
+  post_id
+);
+
+CREATE TABLE IF NOT EXISTS `media` (
+  id INTEGER PRIMARY KEY,
+  sha256 BLOB UNIQUE NOT NULL, -- hex
+  mime_type BLOB NOT NULL,
+  size INTEGER NOT NULL, -- bytes
+  author_id INTEGER NOT NULL, -- uploader
+  uploaded_date INTEGER NOT NULL, -- seconds since UNIX epoch
+  FOREIGN KEY(author_id) REFERENCES author(id)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS `index_media_sha256` ON `media` (
+  sha256
+);
+
+CREATE INDEX IF NOT EXISTS `index_media_author_id` ON `media` (
+  author_id
+);
+
+CREATE TABLE IF NOT EXISTS `post_media` (
+  id INTEGER PRIMARY KEY,
+  post_id INTEGER NOT NULL,
+  media_id INTEGER NOT NULL,
+  FOREIGN KEY(post_id) REFERENCES post(id) ON DELETE CASCADE,
+  FOREIGN KEY(media_id) REFERENCES media(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS `index_post_media` ON `post_media` (
+  post_id,
+  media_id
+);
+
+CREATE INDEX IF NOT EXISTS `index_post_media_media_id` ON `post_media` (
+  media_id
+);
+
+CREATE TABLE IF NOT EXISTS `author_icon` (
+  id INTEGER PRIMARY KEY,
+  author_id INTEGER NOT NULL,
+  size INTEGER NOT NULL, -- pixels (square)
+  media_id INTEGER NOT NULL,
+  FOREIGN KEY(author_id) REFERENCES author(id) ON DELETE CASCADE,
+  FOREIGN KEY(media_id) REFERENCES media(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS `index_author_icon_author_id_size` ON `author_icon` (
+  author_id,
+  size
+);
+
+CREATE INDEX IF NOT EXISTS `index_author_icon_media_id` ON `author_icon` (
+  media_id
+);
+
+CREATE TABLE IF NOT EXISTS `channel_icon` (
+  id INTEGER PRIMARY KEY,
+  channel_id INTEGER NOT NULL,
+  size INTEGER NOT NULL, -- pixels (square)
+  media_id INTEGER NOT NULL,
+  FOREIGN KEY(channel_id) REFERENCES channel(id) ON DELETE CASCADE,
+  FOREIGN KEY(media_id) REFERENCES media(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS `index_channel_icon_channel_id_size` ON `channel_icon` (
+  channel_id,
+  size
+);
+
+CREATE INDEX IF NOT EXISTS `index_channel_icon_media_id` ON `channel_icon` (
+  media_id
+);
+
+CREATE TABLE IF NOT EXISTS `comment` (
+  id INTEGER PRIMARY KEY,
+  uuid BLOB UNIQUE NOT NULL,
+  post_id INTEGER NOT NULL,
+  parent_id INTEGER, -- NULL for top-level comments
+  author_id INTEGER NOT NULL,
+  created_date INTEGER NOT NULL, -- seconds since UNIX epoch
+  is_deleted INTEGER NOT NULL DEFAULT 0,
+  comment_text BLOB NOT NULL DEFAULT '',
+  FOREIGN KEY(post_id) REFERENCES post(id) ON DELETE CASCADE,
+  FOREIGN KEY(parent_id) REFERENCES comment(id) ON DELETE CASCADE,
+  FOREIGN KEY(author_id) REFERENCES author(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS `index_comment_uuid` ON `comment` (
+  uuid
+);
+
+CREATE INDEX IF NOT EXISTS `index_comment_post_id` ON `comment` (
+  post_id
+);
+
+CREATE INDEX IF NOT EXISTS `index_comment_parent_id` ON `comment` (
+  parent_id
+);
+
+CREATE TABLE IF NOT EXISTS `channel_transfer` (
+  id INTEGER PRIMARY KEY,
+  uuid BLOB UNIQUE NOT NULL,
+  channel_id INTEGER NOT NULL,
+  from_author_id INTEGER NOT NULL, -- offering owner
+  to_author_id INTEGER NOT NULL, -- recipient
+  created_date INTEGER NOT NULL, -- seconds since UNIX epoch
+  expires_date INTEGER NOT NULL, -- seconds since UNIX epoch
+  FOREIGN KEY(channel_id) REFERENCES channel(id) ON DELETE CASCADE,
+  FOREIGN KEY(from_author_id) REFERENCES author(id) ON DELETE CASCADE,
+  FOREIGN KEY(to_author_id) REFERENCES author(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS `index_channel_transfer_uuid` ON `channel_transfer` (
+  uuid
+);
+
+CREATE INDEX IF NOT EXISTS `index_channel_transfer_channel_id` ON `channel_transfer` (
+  channel_id
+);
+
+CREATE TABLE IF NOT EXISTS `channel_history` (
+  id INTEGER PRIMARY KEY,
+  channel_id INTEGER NOT NULL,
+  event BLOB NOT NULL, -- e.g. 'ownership_transfer'
+  author_id INTEGER NOT NULL, -- acting author
+  target_author_id INTEGER, -- e.g. the new owner
+  created_date INTEGER NOT NULL, -- seconds since UNIX epoch
+  FOREIGN KEY(channel_id) REFERENCES channel(id) ON DELETE CASCADE,
+  FOREIGN KEY(author_id) REFERENCES author(id) ON DELETE CASCADE,
+  FOREIGN KEY(target_author_id) REFERENCES author(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS `index_channel_history_channel_id` ON `channel_history` (
+  channel_id
+);
+
+CREATE TABLE IF NOT EXISTS `translation_group` (
+  id INTEGER PRIMARY KEY,
+  created_date INTEGER NOT NULL -- seconds since UNIX epoch
+);
+
+CREATE TABLE IF NOT EXISTS `post_translation` (
+  id INTEGER PRIMARY KEY,
+  post_id INTEGER UNIQUE NOT NULL, -- a post belongs to at most one group
+  group_id INTEGER NOT NULL,
+  FOREIGN KEY(post_id) REFERENCES post(id) ON DELETE CASCADE,
+  FOREIGN KEY(group_id) REFERENCES translation_group(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS `index_post_translation_group_id` ON `post_translation` (
+  group_id
+);
+
+CREATE TABLE IF NOT EXISTS `channel_pin` (
+  id INTEGER PRIMARY KEY,
+  channel_id INTEGER NOT NULL,
+  post_id INTEGER UNIQUE NOT NULL,
+  position INTEGER NOT NULL, -- 0 is shown first
+  FOREIGN KEY(channel_id) REFERENCES channel(id) ON DELETE CASCADE,
+  FOREIGN KEY(post_id) REFERENCES post(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS `index_channel_pin_channel_id` ON `channel_pin` (
+  channel_id
+);
+
+CREATE TABLE IF NOT EXISTS `featured_post` (
+  id INTEGER PRIMARY KEY,
+  post_id INTEGER UNIQUE NOT NULL,
+  position INTEGER NOT NULL, -- 0 is shown first
+  FOREIGN KEY(post_id) REFERENCES post(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS `post_label` (
+  id INTEGER PRIMARY KEY,
+  post_id INTEGER NOT NULL,
+  label BLOB NOT NULL,
+  is_admin_label INTEGER NOT NULL DEFAULT 0, -- applied by admins, not editable by authors
+  FOREIGN KEY(post_id) REFERENCES post(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS `index_post_label_post_id_label_is_admin_label` ON `post_label` (
+  post_id,
+  label,
+  is_admin_label
 );
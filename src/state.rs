@@ -6,6 +6,9 @@ use sha2::Sha256;
 use hmac::{Hmac, Mac};
 
 use crate::db::RusqliteConnection;
+use crate::markdown::MarkdownCache;
+use crate::media::MediaStore;
+use crate::frontend::Frontend;
 
 
 type HmacSha256 = Hmac<Sha256>;
@@ -61,4 +64,7 @@ impl PrimarySecret {
 pub struct AppState {
     pub db_connection: Mutex<RusqliteConnection>,
     pub primary_secret: PrimarySecret,
+    pub markdown_cache: Mutex<MarkdownCache>,
+    pub media_store: MediaStore,
+    pub frontend: Frontend,
 }
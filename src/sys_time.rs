@@ -7,3 +7,128 @@ pub fn get_sys_time_in_secs() -> u64 {
         Err(_) => panic!("SystemTime before UNIX EPOCH!"),
     }
 }
+
+/// Converts days since UNIX epoch into a (year, month, day) date.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z % 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Converts a (year, month, day) date into days since UNIX epoch. Dates before 1970 are not supported.
+fn days_from_civil(year: u64, month: u64, day: u64) -> Option<u64> {
+    // http://howardhinnant.github.io/date_algorithms.html
+    let year = if month <= 2 { year.checked_sub(1)? } else { year };
+    let era = year / 400;
+    let yoe = year % 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era.checked_mul(146097)?.checked_add(doe)?.checked_sub(719468)
+}
+
+/// Formats seconds since UNIX epoch as an RFC 3339 date in UTC (`2006-01-02T15:04:05Z`).
+pub fn format_rfc3339(secs: u64) -> String {
+    let (year, month, day) = civil_from_days(secs / 86400);
+    let time = secs % 86400;
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, time / 3600, time / 60 % 60, time % 60)
+}
+
+/// Formats seconds since UNIX epoch as an RFC 2822 date in UTC (`Mon, 02 Jan 2006 15:04:05 +0000`).
+pub fn format_rfc2822(secs: u64) -> String {
+    format_weekday_date(secs, "+0000")
+}
+
+/// Formats seconds since UNIX epoch as an HTTP date (`Mon, 02 Jan 2006 15:04:05 GMT`).
+pub fn format_http_date(secs: u64) -> String {
+    format_weekday_date(secs, "GMT")
+}
+
+/// Parses an HTTP date (`Mon, 02 Jan 2006 15:04:05 GMT`) into seconds since UNIX epoch.
+/// The obsolete RFC 850 and asctime formats are not accepted, nor are years outside 1970 to 9999.
+pub fn parse_http_date(date: &str) -> Option<u64> {
+    static MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+    let (_weekday, date) = date.trim().split_once(", ")?;
+    let parts: Vec<&str> = date.split(' ').collect();
+    if parts.len() != 5 || parts[4] != "GMT" {
+        return None;
+    }
+    let day: u64 = parts[0].parse().ok()?;
+    let month = MONTHS.iter().position(|month| *month == parts[1])? as u64 + 1;
+    let year: u64 = parts[2].parse().ok()?;
+    if !(1970..=9999).contains(&year) {
+        return None;
+    }
+    let time: Vec<u64> = parts[3].split(':').map(|part| part.parse().ok()).collect::<Option<_>>()?;
+    if !(1..=31).contains(&day) || time.len() != 3 || time[0] > 23 || time[1] > 59 || time[2] > 60 {
+        return None;
+    }
+    let days = days_from_civil(year, month, day)?;
+    days.checked_mul(86400)?.checked_add(time[0] * 3600 + time[1] * 60 + time[2])
+}
+
+fn format_weekday_date(secs: u64, zone: &str) -> String {
+    static WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    static MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+    let days = secs / 86400;
+    let (year, month, day) = civil_from_days(days);
+    let time = secs % 86400;
+    format!(
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} {}",
+        WEEKDAYS[(days % 7) as usize], day, MONTHS[(month - 1) as usize], year, time / 3600, time / 60 % 60, time % 60, zone,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_http_date_valid() {
+        assert_eq!(parse_http_date("Thu, 01 Jan 1970 00:00:00 GMT"), Some(0));
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"), Some(784111777));
+        assert_eq!(parse_http_date("Tue, 29 Feb 2000 12:00:00 GMT"), Some(951825600));
+        assert_eq!(parse_http_date("Fri, 31 Dec 9999 23:59:59 GMT"), Some(253402300799));
+    }
+
+    #[test]
+    fn parse_http_date_round_trip() {
+        for secs in [0, 951825600, 1792373626, 253402300799] {
+            assert_eq!(parse_http_date(&format_http_date(secs)), Some(secs));
+        }
+    }
+
+    #[test]
+    fn parse_http_date_malformed() {
+        assert_eq!(parse_http_date(""), None);
+        assert_eq!(parse_http_date("yesterday"), None);
+        assert_eq!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"), None);
+        assert_eq!(parse_http_date("Sun Nov  6 08:49:37 1994"), None);
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49:37 +0000"), None);
+        assert_eq!(parse_http_date("Sun, 06 Foo 1994 08:49:37 GMT"), None);
+        assert_eq!(parse_http_date("Sun, 32 Nov 1994 08:49:37 GMT"), None);
+        assert_eq!(parse_http_date("Sun, 00 Nov 1994 08:49:37 GMT"), None);
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 24:00:00 GMT"), None);
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49 GMT"), None);
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:-1:37 GMT"), None);
+    }
+
+    #[test]
+    fn parse_http_date_out_of_range() {
+        assert_eq!(parse_http_date("Wed, 31 Dec 1969 23:59:59 GMT"), None);
+        assert_eq!(parse_http_date("Sat, 01 Jan 10000 00:00:00 GMT"), None);
+        assert_eq!(parse_http_date("Mon, 01 Jan 18446744073709551615 00:00:00 GMT"), None);
+        assert_eq!(parse_http_date("Mon, 01 Jan 99999999999999999999 00:00:00 GMT"), None);
+        assert_eq!(parse_http_date("Mon, 01 Jan 0 00:00:00 GMT"), None);
+    }
+}
@@ -0,0 +1,124 @@
+
+use rusqlite::OptionalExtension;
+
+use crate::api::v1::types::{
+    ChannelSummary,
+    PostTranslation,
+};
+use crate::sys_time;
+use crate::icons;
+
+
+/// Returns the translation group of a post, if it is linked to any.
+pub fn get_translation_group(conn: &rusqlite::Connection, post_id: u32) -> rusqlite::Result<Option<u32>> {
+    conn.query_row(
+        "SELECT group_id FROM post_translation WHERE post_id = ?",
+        [post_id],
+        |row| row.get(0),
+    ).optional()
+}
+
+/// Links two posts as translations of each other.
+/// Groups the posts already belong to are merged.
+pub fn link_translation(conn: &rusqlite::Connection, post_id: u32, other_post_id: u32) -> anyhow::Result<()> {
+    if post_id == other_post_id {
+        return Err(anyhow::anyhow!("Cannot link a post to itself"));
+    }
+
+    let group_id = get_translation_group(conn, post_id)?;
+    let other_group_id = get_translation_group(conn, other_post_id)?;
+
+    match (group_id, other_group_id) {
+        (Some(group_id), Some(other_group_id)) => {
+            if group_id == other_group_id {
+                return Err(anyhow::anyhow!("Posts are already linked"));
+            }
+            conn.execute(
+                "UPDATE post_translation SET group_id = ? WHERE group_id = ?",
+                (group_id, other_group_id),
+            )?;
+            conn.execute("DELETE FROM translation_group WHERE id = ?", [other_group_id])?;
+        },
+        (Some(group_id), None) => {
+            conn.execute(
+                "INSERT INTO post_translation (post_id, group_id) VALUES (?, ?)",
+                (other_post_id, group_id),
+            )?;
+        },
+        (None, Some(other_group_id)) => {
+            conn.execute(
+                "INSERT INTO post_translation (post_id, group_id) VALUES (?, ?)",
+                (post_id, other_group_id),
+            )?;
+        },
+        (None, None) => {
+            conn.execute(
+                "INSERT INTO translation_group (created_date) VALUES (?)",
+                [sys_time::get_sys_time_in_secs()],
+            )?;
+            let group_id = conn.last_insert_rowid();
+            for post_id in [post_id, other_post_id] {
+                conn.execute(
+                    "INSERT INTO post_translation (post_id, group_id) VALUES (?, ?)",
+                    (post_id, group_id),
+                )?;
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Removes a post from its translation group.
+/// A group left with a single post is removed.
+pub fn unlink_translation(conn: &rusqlite::Connection, post_id: u32) -> anyhow::Result<()> {
+    let group_id = get_translation_group(conn, post_id)?
+        .ok_or_else(|| anyhow::anyhow!("Post is not linked to any translation"))?;
+
+    conn.execute("DELETE FROM post_translation WHERE post_id = ?", [post_id])?;
+
+    let remaining: u32 = conn.query_row(
+        "SELECT COUNT(*) FROM post_translation WHERE group_id = ?",
+        [group_id],
+        |row| row.get(0),
+    )?;
+    if remaining < 2 {
+        conn.execute("DELETE FROM translation_group WHERE id = ?", [group_id])?;
+    }
+    Ok(())
+}
+
+/// Lists the other posts in the translation group of a post.
+/// Deleted posts and posts in deleted channels are skipped.
+pub fn get_post_translations(conn: &rusqlite::Connection, post_id: u32) -> rusqlite::Result<Vec<PostTranslation>> {
+    let mut stmt = conn.prepare(
+        &format!(
+            "
+                SELECT post.uuid, post_slug.slug, channel.uuid, channel.handle, channel.name, channel.language_code,
+                {channel_icon},
+                (SELECT revision.title FROM revision WHERE revision.post_id = post.id AND revision.is_deleted = 0 ORDER BY revision.created_date DESC, revision.id DESC LIMIT 1),
+                COALESCE(post.language_code, channel.language_code)
+                FROM post_translation AS self, post_translation AS sibling, post, channel
+                LEFT JOIN post_slug ON post_slug.post_id = post.id AND post_slug.is_current = 1
+                WHERE self.post_id = ? AND sibling.group_id = self.group_id AND sibling.post_id != self.post_id
+                AND post.id = sibling.post_id AND post.is_deleted = 0 AND post.visibility = 'public' AND channel.id = post.channel_id AND channel.is_deleted = 0
+                ORDER BY COALESCE(post.language_code, channel.language_code) ASC, post.id ASC
+            ",
+            channel_icon = icons::channel_summary_icon_query("channel.id"),
+        )
+    )?;
+    let translations = stmt.query_map([post_id], |row| {
+        let post_uuid: String = row.get(0)?;
+        let slug: Option<String> = row.get(1)?;
+        let channel_uuid: String = row.get(2)?;
+        let channel_handle: String = row.get(3)?;
+        let channel_name: String = row.get(4)?;
+        let channel_lang: String = row.get(5)?;
+        let channel_icon_sha256: Option<String> = row.get(6)?;
+        let title: Option<String> = row.get(7)?;
+        let lang: String = row.get(8)?;
+
+        let channel = ChannelSummary::new(&channel_uuid, &channel_handle, &channel_name, &channel_lang, channel_icon_sha256.as_deref());
+        Ok(PostTranslation::new(&post_uuid, slug.as_deref(), &channel, title.as_deref().unwrap_or(""), &lang))
+    })?.collect();
+    translations
+}
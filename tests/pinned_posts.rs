@@ -0,0 +1,75 @@
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{State, Query};
+use axum::response::IntoResponse;
+
+use alarkhabil_server::api::v1::{api_channel_pinned, api_post_featured};
+use alarkhabil_server::db::RusqliteConnection;
+use alarkhabil_server::frontend::Frontend;
+use alarkhabil_server::markdown::MarkdownCache;
+use alarkhabil_server::media::MediaStore;
+use alarkhabil_server::state::{AppState, PrimarySecret};
+
+
+static SQL_SCHEMA_SQLITE: &str = include_str!("../src/sql/schema-sqlite.sql");
+
+fn new_state() -> Arc<AppState> {
+    let db_connection = RusqliteConnection::open(None, Some(SQL_SCHEMA_SQLITE)).unwrap();
+    Arc::new(AppState {
+        db_connection: Mutex::new(db_connection),
+        primary_secret: PrimarySecret::new_random(),
+        markdown_cache: Mutex::new(MarkdownCache::new()),
+        media_store: MediaStore::new(std::env::temp_dir()),
+        frontend: Frontend::new("http://localhost", "Alarkhabil"),
+    })
+}
+
+/// A channel with one post that is both pinned and featured, updated twice after it was published.
+fn insert_updated_post(state: &AppState) {
+    let mut db_connection = state.db_connection.lock().unwrap();
+    let trx = db_connection.transaction().unwrap();
+    trx.execute_batch("
+        INSERT INTO author (id, uuid, name, registered_date) VALUES (1, 'author-uuid', 'author', 100);
+        INSERT INTO channel (id, uuid, handle, name, created_date, language_code) VALUES (1, 'channel-uuid', 'channel', 'channel', 100, 'en');
+        INSERT INTO channel_author (channel_id, author_id) VALUES (1, 1);
+        INSERT INTO post (id, uuid, channel_id, published_date, updated_date) VALUES (1, 'post-uuid', 1, 100, 300);
+        INSERT INTO revision (uuid, post_id, author_id, created_date, title, revision_text) VALUES ('revision-1', 1, 1, 100, 'v1', 'text');
+        INSERT INTO revision (uuid, post_id, author_id, created_date, title, revision_text) VALUES ('revision-2', 1, 1, 200, 'v2', 'text');
+        INSERT INTO revision (uuid, post_id, author_id, created_date, title, revision_text) VALUES ('revision-3', 1, 1, 300, 'v3', 'text');
+        INSERT INTO channel_pin (channel_id, post_id, position) VALUES (1, 1, 0);
+        INSERT INTO featured_post (post_id, position) VALUES (1, 0);
+    ").unwrap();
+    trx.commit().unwrap();
+}
+
+async fn response_json(response: impl IntoResponse) -> serde_json::Value {
+    let body = hyper::body::to_bytes(response.into_response().into_body()).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn pinned_posts_show_latest_revision() {
+    let state = new_state();
+    insert_updated_post(&state);
+
+    let params = HashMap::from([("uuid".to_string(), "channel-uuid".to_string())]);
+    let posts = response_json(api_channel_pinned(State(state), Query(params)).await).await;
+
+    assert_eq!(posts[0]["title"], "v3");
+    assert_eq!(posts[0]["revision_uuid"], "revision-3");
+    assert_eq!(posts[0]["revision_date"], 300);
+}
+
+#[tokio::test]
+async fn featured_posts_show_latest_revision() {
+    let state = new_state();
+    insert_updated_post(&state);
+
+    let posts = response_json(api_post_featured(State(state), Query(HashMap::new())).await).await;
+
+    assert_eq!(posts[0]["title"], "v3");
+    assert_eq!(posts[0]["revision_uuid"], "revision-3");
+    assert_eq!(posts[0]["revision_date"], 300);
+}